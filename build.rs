@@ -2,7 +2,7 @@ fn main() {
     protobuf_codegen::Codegen::new()
         .pure()
         .include("proto")
-        .inputs(&[
+        .inputs([
             "proto/InitConnect.proto",
             "proto/Common.proto",
             "proto/Qot_Common.proto",
@@ -29,6 +29,54 @@ fn main() {
             "proto/KeepAlive.proto",
             "proto/Qot_UpdateRT.proto",
             "proto/Qot_UpdateKL.proto",
+            "proto/Qot_GetCapitalFlow.proto",
+            "proto/Qot_GetCapitalDistribution.proto",
+            "proto/Qot_GetStaticInfo.proto",
+            "proto/Qot_GetCodeChange.proto",
+            "proto/Qot_GetPlateSet.proto",
+            "proto/Qot_GetOwnerPlate.proto",
+            "proto/Qot_GetReference.proto",
+            "proto/Qot_RequestRehab.proto",
+            "proto/Qot_RequestTradeDate.proto",
+            "proto/Qot_GetMarketState.proto",
+            "proto/Qot_GetSuspend.proto",
+            "proto/Qot_GetFutureInfo.proto",
+            "proto/Qot_GetHoldingChangeList.proto",
+            "proto/Qot_GetHistoryKLPoints.proto",
+            "proto/Qot_GetKL.proto",
+            "proto/Qot_GetRT.proto",
+            "proto/Qot_GetSubInfo.proto",
+            "proto/Qot_RegQotPush.proto",
+            "proto/GetDelayStatistics.proto",
+            "proto/GetUserInfo.proto",
+            "proto/Verification.proto",
+            "proto/Notify.proto",
+            "proto/Qot_GetBroker.proto",
+            "proto/Qot_GetHistoryKL.proto",
+            "proto/Qot_GetOptionChain.proto",
+            "proto/Qot_GetOptionExpirationDate.proto",
+            "proto/Qot_GetOrderBook.proto",
+            "proto/Qot_GetRehab.proto",
+            "proto/Qot_GetTicker.proto",
+            "proto/Qot_GetWarrant.proto",
+            "proto/Qot_RequestHistoryKL.proto",
+            "proto/Qot_RequestHistoryKLQuota.proto",
+            "proto/Qot_UpdateBroker.proto",
+            "proto/Qot_UpdateOrderBook.proto",
+            "proto/Qot_UpdatePriceReminder.proto",
+            "proto/Qot_UpdateTicker.proto",
+            "proto/TestCmd.proto",
+            "proto/Trd_GetAccList.proto",
+            "proto/Trd_GetFunds.proto",
+            "proto/Trd_GetHistoryOrderFillList.proto",
+            "proto/Trd_GetMarginRatio.proto",
+            "proto/Trd_GetOrderFillList.proto",
+            "proto/Trd_GetOrderList.proto",
+            "proto/Trd_Notify.proto",
+            "proto/Trd_ReconfirmOrder.proto",
+            "proto/Trd_SubAccPush.proto",
+            "proto/Trd_UpdateOrder.proto",
+            "proto/Trd_UpdateOrderFill.proto",
         ])
         .cargo_out_dir("rust_protobuf_protos")
         .run_from_script();
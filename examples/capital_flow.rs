@@ -0,0 +1,30 @@
+use futuapi_rs::{
+    action::{
+        capital_distribution::GetCapitalDistributionRequest, capital_flow::GetCapitalFlowRequest,
+    },
+    client, ConnectOptions, Result,
+};
+
+#[tokio::main]
+pub async fn main() -> Result<()> {
+    let mut qot_client = client::qot_connect("127.0.0.1:11111", ConnectOptions::default()).await?;
+
+    let get_capital_flow_resp = qot_client
+        .get_capital_flow(GetCapitalFlowRequest::new("HK.00700".try_into().unwrap()))
+        .await?;
+    println!("net flow: {}", get_capital_flow_resp.net_flow());
+
+    let get_capital_distribution_resp = qot_client
+        .get_capital_distribution(GetCapitalDistributionRequest::new(
+            "HK.00700".try_into().unwrap(),
+        ))
+        .await?;
+    println!(
+        "large order ratio: {:?}",
+        get_capital_distribution_resp
+            .distribution
+            .large_order_ratio()
+    );
+
+    Ok(())
+}
@@ -0,0 +1,33 @@
+use futuapi_rs::{
+    action::delay_statistics::GetDelayStatisticsRequest,
+    client, ConnectOptions,
+    GetDelayStatistics::{DelayStatisticsType, QotPushStage},
+    Result,
+};
+
+#[tokio::main]
+pub async fn main() -> Result<()> {
+    let mut qot_client = client::qot_connect("127.0.0.1:11111", ConnectOptions::default()).await?;
+
+    let get_delay_statistics_resp = qot_client
+        .get_delay_statistics(GetDelayStatisticsRequest::new(
+            vec![
+                DelayStatisticsType::DelayStatisticsType_QotPush,
+                DelayStatisticsType::DelayStatisticsType_ReqReply,
+                DelayStatisticsType::DelayStatisticsType_PlaceOrder,
+            ],
+            Some(QotPushStage::QotPushStage_SR2CS),
+            Vec::new(),
+        ))
+        .await?;
+
+    for delay_statistics in &get_delay_statistics_resp.qot_push_statistics_list {
+        println!("{}", delay_statistics);
+    }
+
+    for item in &get_delay_statistics_resp.req_reply_statistics_list {
+        println!("{:?}", item);
+    }
+
+    Ok(())
+}
@@ -3,13 +3,13 @@ use futuapi_rs::{
         common::{TrdFilterConditions, TrdHeader},
         history_order_list::GetHistoryOrderListRequest,
     },
-    client, Result,
+    client, ConnectOptions, Result,
     Trd_Common::{OrderStatus, TrdEnv, TrdMarket},
 };
 
 #[tokio::main]
 pub async fn main() -> Result<()> {
-    let mut trd_client = client::trd_connect("127.0.0.1:11111").await?;
+    let mut trd_client = client::trd_connect("127.0.0.1:11111", ConnectOptions::default()).await?;
 
     let resp = trd_client
         .get_history_order_list(GetHistoryOrderListRequest::new(
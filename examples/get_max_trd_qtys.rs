@@ -1,12 +1,12 @@
 use futuapi_rs::{
     action::{common::TrdHeader, max_trd_qtys::GetMaxTrdQtysRequest},
-    client, Result,
+    client, ConnectOptions, Result,
     Trd_Common::{OrderType, TrdEnv, TrdMarket, TrdSecMarket},
 };
 
 #[tokio::main]
 pub async fn main() -> Result<()> {
-    let mut trd_client = client::trd_connect("127.0.0.1:11111").await?;
+    let mut trd_client = client::trd_connect("127.0.0.1:11111", ConnectOptions::default()).await?;
     let get_max_trd_qtys_resp = trd_client
         .get_max_trd_qtys(GetMaxTrdQtysRequest::new(
             TrdHeader {
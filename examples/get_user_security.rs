@@ -1,8 +1,10 @@
-use futuapi_rs::{action::user_security::get::GetUserSecurityRequest, client, Result};
+use futuapi_rs::{
+    action::user_security::get::GetUserSecurityRequest, client, ConnectOptions, Result,
+};
 
 #[tokio::main]
 pub async fn main() -> Result<()> {
-    let mut qot_client = client::qot_connect("127.0.0.1:11111").await?;
+    let mut qot_client = client::qot_connect("127.0.0.1:11111", ConnectOptions::default()).await?;
     let get_user_security_resp = qot_client
         .get_user_security(GetUserSecurityRequest::new("A".into()))
         .await?;
@@ -1,8 +1,8 @@
-use futuapi_rs::{action::ipo::GetIpoListRequest, client, Result};
+use futuapi_rs::{action::ipo::GetIpoListRequest, client, ConnectOptions, Result};
 
 #[tokio::main]
 pub async fn main() -> Result<()> {
-    let mut qot_client = client::qot_connect("127.0.0.1:11111").await?;
+    let mut qot_client = client::qot_connect("127.0.0.1:11111", ConnectOptions::default()).await?;
     let get_ipo_list_resp = qot_client
         .get_ipo_list(GetIpoListRequest::new(
             futuapi_rs::Qot_Common::QotMarket::QotMarket_US_Security,
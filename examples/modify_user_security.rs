@@ -1,11 +1,11 @@
 use futuapi_rs::{
-    action::user_security::modify::ModifyUserSecurityRequest, client,
+    action::user_security::modify::ModifyUserSecurityRequest, client, ConnectOptions,
     Qot_ModifyUserSecurity::ModifyUserSecurityOp, Result,
 };
 
 #[tokio::main]
 pub async fn main() -> Result<()> {
-    let mut qot_client = client::qot_connect("127.0.0.1:11111").await?;
+    let mut qot_client = client::qot_connect("127.0.0.1:11111", ConnectOptions::default()).await?;
     qot_client
         .modify_user_security(ModifyUserSecurityRequest::new(
             "自选".to_string(),
@@ -1,12 +1,14 @@
 use futuapi_rs::{
-    client, Result,
-    Trd_Common::{OrderType, TimeInForce, TrdEnv, TrdMarket, TrdSecMarket, TrdSide},
+    client, ConnectOptions, Result,
+    Trd_Common::{OrderType, SecurityFirm, TimeInForce, TrdEnv, TrdMarket, TrdSecMarket, TrdSide},
 };
 
 #[tokio::main]
 pub async fn main() -> Result<()> {
-    let mut trd_client = client::trd_connect("127.0.0.1:11111").await?;
-    trd_client.unlock("123456".into()).await?;
+    let mut trd_client = client::trd_connect("127.0.0.1:11111", ConnectOptions::default()).await?;
+    trd_client
+        .unlock(SecurityFirm::SecurityFirm_FutuSecurities, "123456".into())
+        .await?;
 
     let place_order_resp = trd_client
         .place_order(
@@ -1,6 +1,6 @@
 use futuapi_rs::{
     action::price_reminder::{get::GetPriceReminderRequest, set::SetPriceReminderRequest},
-    client,
+    client, ConnectOptions,
     Qot_Common::{PriceReminderFreq, PriceReminderType},
     Qot_SetPriceReminder::SetPriceReminderOp,
     Result,
@@ -8,7 +8,7 @@ use futuapi_rs::{
 
 #[tokio::main]
 pub async fn main() -> Result<()> {
-    let mut qot_client = client::qot_connect("127.0.0.1:11111").await?;
+    let mut qot_client = client::qot_connect("127.0.0.1:11111", ConnectOptions::default()).await?;
     let set_price_reminder_resp = qot_client
         .set_price_reminder(SetPriceReminderRequest::new(
             "US.CEI".try_into().unwrap(),
@@ -1,13 +1,13 @@
 use futuapi_rs::{
     action::{common::Security, security_snapshot::GetSecuritySnapshotRequest},
-    client,
+    client, ConnectOptions,
     Qot_Common::QotMarket,
     Result,
 };
 
 #[tokio::main]
 pub async fn main() -> Result<()> {
-    let mut qot_client = client::qot_connect("127.0.0.1:11111").await?;
+    let mut qot_client = client::qot_connect("127.0.0.1:11111", ConnectOptions::default()).await?;
 
     let get_security_snapshot_resp = qot_client
         .get_security_snapshot(GetSecuritySnapshotRequest::new(vec![Security {
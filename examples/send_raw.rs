@@ -0,0 +1,28 @@
+use futuapi_rs::{
+    client,
+    Common::RetType,
+    ConnectOptions, Result,
+    Trd_GetAccList::{Request, Response, C2S},
+};
+use protobuf::MessageField;
+
+#[tokio::main]
+pub async fn main() -> Result<()> {
+    let mut trd_client = client::trd_connect("127.0.0.1:11111", ConnectOptions::default()).await?;
+
+    let mut c2s = C2S::new();
+    c2s.set_userID(0);
+    let mut req = Request::new();
+    req.c2s = MessageField::some(c2s);
+
+    let resp: Response = trd_client.send_raw(2001, req).await?;
+    if resp.retType() != RetType::RetType_Succeed as i32 {
+        return Err(resp.retMsg().into());
+    }
+
+    for acc in resp.s2c.accList.iter() {
+        println!("{:?}", acc);
+    }
+
+    Ok(())
+}
@@ -1,6 +1,6 @@
 use futuapi_rs::{
     action::stock_filter::{AccumulateFilter, BaseFilter, GetStockFilterRequest},
-    client,
+    client, ConnectOptions,
     Qot_Common::QotMarket,
     Qot_StockFilter::{AccumulateField, SortDir, StockField},
     Result,
@@ -9,7 +9,7 @@ use std::collections::HashMap;
 
 #[tokio::main]
 pub async fn main() -> Result<()> {
-    let mut qot_client = client::qot_connect("127.0.0.1:11111").await?;
+    let mut qot_client = client::qot_connect("127.0.0.1:11111", ConnectOptions::default()).await?;
     let code_info: Vec<_> = qot_client
         .get_stock_filter(GetStockFilterRequest::new(
             0,
@@ -1,11 +1,11 @@
 use futuapi_rs::{
-    action::subscribe::SubscribeRequest, client, frame::Error, Qot_Common::SubType, Result,
-    UpdateResponse,
+    action::subscribe::SubscribeRequest, client, frame::Error, ConnectOptions, Qot_Common::SubType,
+    Result, UpdateResponse,
 };
 
 #[tokio::main]
 pub async fn main() -> Result<()> {
-    let sub_client = client::sub_connect("127.0.0.1:11111").await?;
+    let sub_client = client::sub_connect("127.0.0.1:11111", ConnectOptions::default()).await?;
     let mut sub = sub_client
         .subscribe(SubscribeRequest::new(
             vec!["HK.00700".try_into().unwrap()],
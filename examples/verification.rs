@@ -0,0 +1,31 @@
+use futuapi_rs::{
+    action::verification::VerificationRequest, client, ConnectOptions, Result,
+    Verification::VerificationType,
+};
+use std::io::{self, BufRead};
+
+#[tokio::main]
+pub async fn main() -> Result<()> {
+    let mut qot_client = client::qot_connect("127.0.0.1:11111", ConnectOptions::default()).await?;
+
+    qot_client
+        .verification(VerificationRequest::request(
+            VerificationType::VerificationType_Phone,
+        ))
+        .await?;
+
+    println!("input the verification code:");
+    let mut code = String::new();
+    io::stdin().lock().read_line(&mut code)?;
+
+    qot_client
+        .verification(VerificationRequest::input_and_login(
+            VerificationType::VerificationType_Phone,
+            code.trim().into(),
+        ))
+        .await?;
+
+    println!("verification passed");
+
+    Ok(())
+}
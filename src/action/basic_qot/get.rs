@@ -1,7 +1,6 @@
 use super::super::common::{BasicQot, Security, SecurityVec};
 use crate::{
-    Common::RetType,
-    Frame,
+    FutuRequest,
     Qot_GetBasicQot::{Request, Response, C2S},
 };
 use protobuf::MessageField;
@@ -26,10 +25,6 @@ impl GetBasicQotRequest {
     pub fn new(security_list: Vec<Security>) -> Self {
         GetBasicQotRequest(security_list)
     }
-
-    pub fn into_frame(self) -> Frame<Request> {
-        Frame::new(self.into(), PROTO_ID)
-    }
 }
 
 #[derive(Debug)]
@@ -52,10 +47,10 @@ impl GetBasicQotResponse {
     }
 }
 
-pub fn check_response(resp: Response) -> crate::Result<GetBasicQotResponse> {
-    if resp.retType() == RetType::RetType_Succeed as i32 {
-        return Ok(resp.into());
-    }
+impl FutuRequest for GetBasicQotRequest {
+    const PROTO_ID: u32 = PROTO_ID;
 
-    Err(resp.retMsg().into())
+    type Request = Request;
+    type Response = Response;
+    type Output = GetBasicQotResponse;
 }
@@ -1,5 +1,4 @@
 use super::super::common::BasicQot;
-use crate::Common::RetType;
 use crate::Qot_UpdateBasicQot::Response;
 
 pub const PROTO_ID: u32 = 3005;
@@ -17,11 +16,3 @@ impl From<Response> for UpdateBasicQotResponse {
         UpdateBasicQotResponse(basic_qot_list)
     }
 }
-
-pub fn check_response(resp: Response) -> crate::Result<UpdateBasicQotResponse> {
-    if resp.retType() == RetType::RetType_Succeed as i32 {
-        return Ok(resp.into());
-    }
-
-    Err(resp.retMsg().into())
-}
@@ -0,0 +1,119 @@
+use super::common::Security;
+use crate::{
+    FutuRequest,
+    Qot_GetCapitalDistribution::{Request, Response, C2S},
+};
+use protobuf::MessageField;
+
+const PROTO_ID: u32 = 3212;
+
+#[derive(Debug)]
+pub struct GetCapitalDistributionRequest(Security);
+
+impl Into<Request> for GetCapitalDistributionRequest {
+    fn into(self) -> Request {
+        let mut req = Request::new();
+        let mut c2s = C2S::new();
+        c2s.security = MessageField::some(self.0.into());
+        req.c2s = MessageField::some(c2s);
+
+        req
+    }
+}
+
+impl GetCapitalDistributionRequest {
+    pub fn new(security: Security) -> Self {
+        GetCapitalDistributionRequest(security)
+    }
+}
+
+#[derive(Debug, Default, Clone)]
+pub struct CapitalDistribution {
+    pub capital_in_super: Option<f64>, // 流入资金额度，特大单，older OpenD leaves it out
+    pub capital_in_big: f64,           // 流入资金额度，大单
+    pub capital_in_mid: f64,           // 流入资金额度，中单
+    pub capital_in_small: f64,         // 流入资金额度，小单
+    pub capital_out_super: Option<f64>, // 流出资金额度，特大单，older OpenD leaves it out
+    pub capital_out_big: f64,          // 流出资金额度，大单
+    pub capital_out_mid: f64,          // 流出资金额度，中单
+    pub capital_out_small: f64,        // 流出资金额度，小单
+}
+
+impl CapitalDistribution {
+    /// None when OpenD doesn't report the super bucket.
+    pub fn net_in_super(&self) -> Option<f64> {
+        Some(self.capital_in_super? - self.capital_out_super?)
+    }
+
+    pub fn net_in_big(&self) -> f64 {
+        self.capital_in_big - self.capital_out_big
+    }
+
+    pub fn net_in_mid(&self) -> f64 {
+        self.capital_in_mid - self.capital_out_mid
+    }
+
+    pub fn net_in_small(&self) -> f64 {
+        self.capital_in_small - self.capital_out_small
+    }
+
+    pub fn net_in(&self) -> f64 {
+        self.net_in_super().unwrap_or(0.0)
+            + self.net_in_big()
+            + self.net_in_mid()
+            + self.net_in_small()
+    }
+
+    /// Share of super and big orders (in and out) in the total traded amount, `None` when
+    /// nothing traded.
+    pub fn large_order_ratio(&self) -> Option<f64> {
+        let capital_super =
+            self.capital_in_super.unwrap_or(0.0) + self.capital_out_super.unwrap_or(0.0);
+        let total = capital_super
+            + self.capital_in_big
+            + self.capital_in_mid
+            + self.capital_in_small
+            + self.capital_out_big
+            + self.capital_out_mid
+            + self.capital_out_small;
+        if total == 0.0 {
+            return None;
+        }
+
+        Some((capital_super + self.capital_in_big + self.capital_out_big) / total)
+    }
+}
+
+#[derive(Debug)]
+pub struct GetCapitalDistributionResponse {
+    pub distribution: CapitalDistribution,
+    pub update_time: Option<String>,
+    pub update_timestamp: Option<f64>,
+}
+
+impl From<Response> for GetCapitalDistributionResponse {
+    fn from(resp: Response) -> Self {
+        GetCapitalDistributionResponse {
+            distribution: CapitalDistribution {
+                capital_in_super: resp.s2c.capitalInSuper,
+                capital_in_big: resp.s2c.capitalInBig(),
+                capital_in_mid: resp.s2c.capitalInMid(),
+                capital_in_small: resp.s2c.capitalInSmall(),
+                capital_out_big: resp.s2c.capitalOutBig(),
+                capital_out_mid: resp.s2c.capitalOutMid(),
+                capital_out_small: resp.s2c.capitalOutSmall(),
+                capital_out_super: resp.s2c.capitalOutSuper,
+            },
+            update_time: resp.s2c.updateTime.to_owned(),
+            update_timestamp: resp.s2c.updateTimestamp,
+        }
+    }
+}
+
+impl FutuRequest for GetCapitalDistributionRequest {
+    const PROTO_ID: u32 = PROTO_ID;
+
+    type Request = Request;
+    type Response = Response;
+    type Output = GetCapitalDistributionResponse;
+}
@@ -0,0 +1,93 @@
+use super::common::Security;
+use crate::{
+    FutuRequest,
+    Qot_GetCapitalFlow::{self, Request, Response, C2S},
+};
+use protobuf::MessageField;
+
+const PROTO_ID: u32 = 3211;
+
+#[derive(Debug)]
+pub struct GetCapitalFlowRequest(Security);
+
+impl Into<Request> for GetCapitalFlowRequest {
+    fn into(self) -> Request {
+        let mut req = Request::new();
+        let mut c2s = C2S::new();
+        c2s.security = MessageField::some(self.0.into());
+        req.c2s = MessageField::some(c2s);
+
+        req
+    }
+}
+
+impl GetCapitalFlowRequest {
+    pub fn new(security: Security) -> Self {
+        GetCapitalFlowRequest(security)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct CapitalFlowItem {
+    pub in_flow: f64,           // 净流入的资金额度
+    pub time: Option<String>,   // 开始时间字符串,以分钟为单位
+    pub timestamp: Option<f64>, // 开始时间戳
+}
+
+impl From<Qot_GetCapitalFlow::CapitalFlowItem> for CapitalFlowItem {
+    fn from(item: Qot_GetCapitalFlow::CapitalFlowItem) -> Self {
+        CapitalFlowItem {
+            in_flow: item.inFlow(),
+            time: item.time,
+            timestamp: item.timestamp,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct GetCapitalFlowResponse {
+    pub flow_item_list: Vec<CapitalFlowItem>,
+    pub last_valid_time: Option<String>,
+    pub last_valid_timestamp: Option<f64>,
+}
+
+impl From<Response> for GetCapitalFlowResponse {
+    fn from(resp: Response) -> Self {
+        let mut flow_item_list = Vec::new();
+        for item in resp.s2c.flowItemList.iter().cloned() {
+            flow_item_list.push(item.into());
+        }
+
+        GetCapitalFlowResponse {
+            flow_item_list,
+            last_valid_time: resp.s2c.lastValidTime.to_owned(),
+            last_valid_timestamp: resp.s2c.lastValidTimestamp,
+        }
+    }
+}
+
+impl GetCapitalFlowResponse {
+    /// Total net inflow over all items.
+    pub fn net_flow(&self) -> f64 {
+        self.flow_item_list.iter().map(|item| item.in_flow).sum()
+    }
+
+    /// Running total of the net inflow, one value per item.
+    pub fn cumulative_net_flow(&self) -> Vec<f64> {
+        self.flow_item_list
+            .iter()
+            .scan(0.0, |acc, item| {
+                *acc += item.in_flow;
+                Some(*acc)
+            })
+            .collect()
+    }
+}
+
+impl FutuRequest for GetCapitalFlowRequest {
+    const PROTO_ID: u32 = PROTO_ID;
+
+    type Request = Request;
+    type Response = Response;
+    type Output = GetCapitalFlowResponse;
+}
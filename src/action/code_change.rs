@@ -0,0 +1,120 @@
+use super::common::{Security, SecurityVec};
+use crate::{
+    FutuRequest,
+    Qot_GetCodeChange::{self, CodeChangeType, Request, Response, TimeFilterType, C2S},
+};
+use protobuf::{Enum, MessageField};
+
+const PROTO_ID: u32 = 3216;
+
+#[derive(Debug)]
+pub struct TimeFilter {
+    pub type_: TimeFilterType,
+    pub begin_time: Option<String>,
+    pub end_time: Option<String>,
+}
+
+impl Into<Qot_GetCodeChange::TimeFilter> for TimeFilter {
+    fn into(self) -> Qot_GetCodeChange::TimeFilter {
+        let mut time_filter = Qot_GetCodeChange::TimeFilter::new();
+        time_filter.set_type(self.type_ as i32);
+        time_filter.beginTime = self.begin_time;
+        time_filter.endTime = self.end_time;
+        time_filter
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct GetCodeChangeRequest {
+    pub security_list: Vec<Security>,
+    pub time_filter_list: Vec<TimeFilter>,
+    pub type_list: Vec<CodeChangeType>,
+}
+
+impl Into<Request> for GetCodeChangeRequest {
+    fn into(self) -> Request {
+        let mut req = Request::new();
+        let mut c2s = C2S::new();
+        c2s.securityList = SecurityVec(self.security_list).into();
+        c2s.timeFilterList = self
+            .time_filter_list
+            .into_iter()
+            .map(|time_filter| time_filter.into())
+            .collect();
+        c2s.typeList = self
+            .type_list
+            .into_iter()
+            .map(|type_| type_ as i32)
+            .collect();
+        req.c2s = MessageField::some(c2s);
+
+        req
+    }
+}
+
+impl GetCodeChangeRequest {
+    pub fn new(
+        security_list: Vec<Security>,
+        time_filter_list: Vec<TimeFilter>,
+        type_list: Vec<CodeChangeType>,
+    ) -> Self {
+        GetCodeChangeRequest {
+            security_list,
+            time_filter_list,
+            type_list,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct CodeChangeInfo {
+    pub type_: CodeChangeType,
+    pub security: Security,         // 主代码，在创业板转主板中表示主板
+    pub related_security: Security, // 关联代码，在创业板转主板中表示创业板，在剩余事件中表示临时代码
+    pub public_time: Option<String>,
+    pub public_timestamp: Option<f64>,
+    pub effective_time: Option<String>,
+    pub effective_timestamp: Option<f64>,
+    pub end_time: Option<String>, // 临时代码交易结束时间
+    pub end_timestamp: Option<f64>,
+}
+
+impl From<Qot_GetCodeChange::CodeChangeInfo> for CodeChangeInfo {
+    fn from(code_change_info: Qot_GetCodeChange::CodeChangeInfo) -> Self {
+        CodeChangeInfo {
+            type_: CodeChangeType::from_i32(code_change_info.type_()).unwrap(),
+            security: code_change_info.security.to_owned().unwrap().into(),
+            related_security: code_change_info.relatedSecurity.to_owned().unwrap().into(),
+            public_time: code_change_info.publicTime,
+            public_timestamp: code_change_info.publicTimestamp,
+            effective_time: code_change_info.effectiveTime,
+            effective_timestamp: code_change_info.effectiveTimestamp,
+            end_time: code_change_info.endTime,
+            end_timestamp: code_change_info.endTimestamp,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct GetCodeChangeResponse {
+    pub code_change_list: Vec<CodeChangeInfo>,
+}
+
+impl From<Response> for GetCodeChangeResponse {
+    fn from(resp: Response) -> Self {
+        let mut code_change_list = Vec::new();
+        for code_change_info in resp.s2c.codeChangeList.iter().cloned() {
+            code_change_list.push(code_change_info.into());
+        }
+
+        GetCodeChangeResponse { code_change_list }
+    }
+}
+
+impl FutuRequest for GetCodeChangeRequest {
+    const PROTO_ID: u32 = PROTO_ID;
+
+    type Request = Request;
+    type Response = Response;
+    type Output = GetCodeChangeResponse;
+}
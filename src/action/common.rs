@@ -1,7 +1,8 @@
 use crate::{
     Common::{self, ProgramStatusType},
     Qot_Common::{
-        self, DarkStatus, ExchType, PlateSetType, QotMarket, SecurityStatus, SecurityType,
+        self, CompanyAct, DarkStatus, ExchType, IndexOptionType, OptionType, PlateSetType,
+        QotMarket, SecurityStatus, SecurityType, SubType, WarrantType,
     },
     Trd_Common::{self, OrderStatus, TrdEnv, TrdMarket, TrdSide},
 };
@@ -38,20 +39,98 @@ impl From<Qot_Common::SecurityStaticBasic> for SecurityStaticBasic {
     }
 }
 
+#[derive(Debug)]
+pub struct WarrantStaticExData {
+    pub type_: WarrantType,
+    pub owner: Security,
+}
+
+impl From<Qot_Common::WarrantStaticExData> for WarrantStaticExData {
+    fn from(warrant_static_ex_data: Qot_Common::WarrantStaticExData) -> Self {
+        WarrantStaticExData {
+            type_: WarrantType::from_i32(warrant_static_ex_data.type_()).unwrap(),
+            owner: warrant_static_ex_data.owner.unwrap().into(),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct OptionStaticExData {
+    pub type_: OptionType,
+    pub owner: Security,
+    pub strike_time: String,
+    pub strike_price: f64,
+    pub suspend: bool,
+    pub market: String,
+    pub strike_timestamp: Option<f64>,
+    pub index_option_type: Option<IndexOptionType>,
+}
+
+impl From<Qot_Common::OptionStaticExData> for OptionStaticExData {
+    fn from(option_static_ex_data: Qot_Common::OptionStaticExData) -> Self {
+        OptionStaticExData {
+            type_: OptionType::from_i32(option_static_ex_data.type_()).unwrap(),
+            owner: option_static_ex_data.owner.to_owned().unwrap().into(),
+            strike_time: option_static_ex_data.strikeTime().into(),
+            strike_price: option_static_ex_data.strikePrice(),
+            suspend: option_static_ex_data.suspend(),
+            market: option_static_ex_data.market().into(),
+            strike_timestamp: option_static_ex_data.strikeTimestamp,
+            index_option_type: if option_static_ex_data.indexOptionType.is_some() {
+                IndexOptionType::from_i32(option_static_ex_data.indexOptionType())
+            } else {
+                None
+            },
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct FutureStaticExData {
+    pub last_trade_time: String,
+    pub last_trade_timestamp: Option<f64>,
+    pub is_main_contract: bool,
+}
+
+impl From<Qot_Common::FutureStaticExData> for FutureStaticExData {
+    fn from(future_static_ex_data: Qot_Common::FutureStaticExData) -> Self {
+        FutureStaticExData {
+            last_trade_time: future_static_ex_data.lastTradeTime().into(),
+            last_trade_timestamp: future_static_ex_data.lastTradeTimestamp,
+            is_main_contract: future_static_ex_data.isMainContract(),
+        }
+    }
+}
+
 #[derive(Debug)]
 pub struct SecurityStaticInfo {
     pub basic: SecurityStaticBasic,
+    pub warrant_ex_data: Option<WarrantStaticExData>,
+    pub option_ex_data: Option<OptionStaticExData>,
+    pub future_ex_data: Option<FutureStaticExData>,
 }
 
 impl From<Qot_Common::SecurityStaticInfo> for SecurityStaticInfo {
     fn from(security_static_info: Qot_Common::SecurityStaticInfo) -> Self {
         SecurityStaticInfo {
             basic: security_static_info.basic.unwrap().into(),
+            warrant_ex_data: security_static_info
+                .warrantExData
+                .into_option()
+                .map(|ex_data| ex_data.into()),
+            option_ex_data: security_static_info
+                .optionExData
+                .into_option()
+                .map(|ex_data| ex_data.into()),
+            future_ex_data: security_static_info
+                .futureExData
+                .into_option()
+                .map(|ex_data| ex_data.into()),
         }
     }
 }
 
-#[derive(Debug, Default)]
+#[derive(Debug, Default, Clone)]
 pub struct PacketID {
     pub conn_id: u64,
     pub serial_no: u32,
@@ -137,7 +216,7 @@ impl From<Trd_Common::Position> for Position {
     }
 }
 
-#[derive(Debug, Default)]
+#[derive(Debug, Default, Clone)]
 pub struct TrdHeader {
     pub trd_env: TrdEnv,
     pub acc_id: u64,
@@ -189,6 +268,7 @@ impl From<Trd_Common::MaxTrdQtys> for MaxTrdQtys {
     }
 }
 
+#[derive(Debug, Clone)]
 pub struct PlateInfo {
     pub plate: Security,
     pub name: String,
@@ -254,8 +334,8 @@ impl TryFrom<&str> for Security {
     }
 }
 
-impl ToString for Security {
-    fn to_string(&self) -> String {
+impl std::fmt::Display for Security {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
         let mut market = "UnKnown";
         match self.market {
             QotMarket::QotMarket_HK_Security => market = "HK",
@@ -267,7 +347,7 @@ impl ToString for Security {
             _ => (),
         }
 
-        format!("{}.{}", market, self.code)
+        write!(f, "{}.{}", market, self.code)
     }
 }
 
@@ -344,7 +424,7 @@ impl From<Common::ProgramStatus> for ProgramStatus {
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct KLine {
     pub time: String,
     pub is_blank: bool,
@@ -478,3 +558,133 @@ impl From<Qot_Common::BasicQot> for BasicQot {
         }
     }
 }
+
+#[derive(Debug, Clone)]
+pub struct Rehab {
+    pub time: String,
+    pub company_act_flag: i64, // 公司行动(CompanyAct)组合标志位
+    pub fwd_factor_a: f64,
+    pub fwd_factor_b: f64,
+    pub bwd_factor_a: f64,
+    pub bwd_factor_b: f64,
+    pub split_base: Option<i32>,
+    pub split_ert: Option<i32>,
+    pub join_base: Option<i32>,
+    pub join_ert: Option<i32>,
+    pub bonus_base: Option<i32>,
+    pub bonus_ert: Option<i32>,
+    pub transfer_base: Option<i32>,
+    pub transfer_ert: Option<i32>,
+    pub allot_base: Option<i32>,
+    pub allot_ert: Option<i32>,
+    pub allot_price: Option<f64>,
+    pub add_base: Option<i32>,
+    pub add_ert: Option<i32>,
+    pub add_price: Option<f64>,
+    pub dividend: Option<f64>,
+    pub sp_dividend: Option<f64>,
+    pub timestamp: Option<f64>,
+}
+
+impl Rehab {
+    pub fn has_company_act(&self, company_act: CompanyAct) -> bool {
+        self.company_act_flag & company_act as i64 != 0
+    }
+}
+
+impl From<Qot_Common::Rehab> for Rehab {
+    fn from(rehab: Qot_Common::Rehab) -> Self {
+        Rehab {
+            time: rehab.time().into(),
+            company_act_flag: rehab.companyActFlag(),
+            fwd_factor_a: rehab.fwdFactorA(),
+            fwd_factor_b: rehab.fwdFactorB(),
+            bwd_factor_a: rehab.bwdFactorA(),
+            bwd_factor_b: rehab.bwdFactorB(),
+            split_base: rehab.splitBase,
+            split_ert: rehab.splitErt,
+            join_base: rehab.joinBase,
+            join_ert: rehab.joinErt,
+            bonus_base: rehab.bonusBase,
+            bonus_ert: rehab.bonusErt,
+            transfer_base: rehab.transferBase,
+            transfer_ert: rehab.transferErt,
+            allot_base: rehab.allotBase,
+            allot_ert: rehab.allotErt,
+            allot_price: rehab.allotPrice,
+            add_base: rehab.addBase,
+            add_ert: rehab.addErt,
+            add_price: rehab.addPrice,
+            dividend: rehab.dividend,
+            sp_dividend: rehab.spDividend,
+            timestamp: rehab.timestamp,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ShareHoldingChange {
+    pub holder_name: String,
+    pub holding_qty: f64,
+    pub holding_ratio: f64, // 当前持股百分比，如20实际对应20%
+    pub change_qty: f64,
+    pub change_ratio: f64, // 较上一次变动百分比，相对于自身的比例
+    pub time: String,
+    pub timestamp: Option<f64>,
+}
+
+impl From<Qot_Common::ShareHoldingChange> for ShareHoldingChange {
+    fn from(share_holding_change: Qot_Common::ShareHoldingChange) -> Self {
+        ShareHoldingChange {
+            holder_name: share_holding_change.holderName().into(),
+            holding_qty: share_holding_change.holdingQty(),
+            holding_ratio: share_holding_change.holdingRatio(),
+            change_qty: share_holding_change.changeQty(),
+            change_ratio: share_holding_change.changeRatio(),
+            time: share_holding_change.time().into(),
+            timestamp: share_holding_change.timestamp,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct SubInfo {
+    pub sub_type: SubType,
+    pub security_list: Vec<Security>,
+}
+
+impl From<Qot_Common::SubInfo> for SubInfo {
+    fn from(sub_info: Qot_Common::SubInfo) -> Self {
+        let mut security_list = Vec::new();
+        for security in sub_info.securityList.iter().cloned() {
+            security_list.push(security.into());
+        }
+
+        SubInfo {
+            sub_type: SubType::from_i32(sub_info.subType()).unwrap(),
+            security_list,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ConnSubInfo {
+    pub sub_info_list: Vec<SubInfo>,
+    pub used_quota: i32,
+    pub is_own_conn_data: bool,
+}
+
+impl From<Qot_Common::ConnSubInfo> for ConnSubInfo {
+    fn from(conn_sub_info: Qot_Common::ConnSubInfo) -> Self {
+        let mut sub_info_list = Vec::new();
+        for sub_info in conn_sub_info.subInfoList.iter().cloned() {
+            sub_info_list.push(sub_info.into());
+        }
+
+        ConnSubInfo {
+            sub_info_list,
+            used_quota: conn_sub_info.usedQuota(),
+            is_own_conn_data: conn_sub_info.isOwnConnData(),
+        }
+    }
+}
@@ -0,0 +1,230 @@
+use crate::{
+    FutuRequest,
+    GetDelayStatistics::{
+        self, DelayStatisticsType, QotPushStage, QotPushType, Request, Response, C2S,
+    },
+};
+use protobuf::{Enum, MessageField};
+use std::fmt;
+
+const PROTO_ID: u32 = 1006;
+
+#[derive(Debug)]
+pub struct GetDelayStatisticsRequest {
+    type_list: Vec<DelayStatisticsType>,
+    qot_push_stage: Option<QotPushStage>,
+    segment_list: Vec<i32>,
+}
+
+impl Into<Request> for GetDelayStatisticsRequest {
+    fn into(self) -> Request {
+        let mut req = Request::new();
+        let mut c2s = C2S::new();
+        c2s.typeList = self
+            .type_list
+            .into_iter()
+            .map(|type_| type_ as i32)
+            .collect();
+
+        if let Some(qot_push_stage) = self.qot_push_stage {
+            c2s.set_qotPushStage(qot_push_stage as i32);
+        }
+
+        c2s.segmentList = self.segment_list;
+        req.c2s = MessageField::some(c2s);
+
+        req
+    }
+}
+
+impl GetDelayStatisticsRequest {
+    /// An empty `segment_list` keeps OpenD's default segments, -1 stands for infinity.
+    pub fn new(
+        type_list: Vec<DelayStatisticsType>,
+        qot_push_stage: Option<QotPushStage>,
+        segment_list: Vec<i32>,
+    ) -> Self {
+        GetDelayStatisticsRequest {
+            type_list,
+            qot_push_stage,
+            segment_list,
+        }
+    }
+}
+
+/// One histogram segment, `[begin, end)` in milliseconds, `end` is -1 for infinity.
+#[derive(Debug, Clone)]
+pub struct DelayStatisticsItem {
+    pub begin: i32,
+    pub end: i32,
+    pub count: i32,
+    pub proportion: f32,       // 占比, %
+    pub cumulative_ratio: f32, // 累计占比, %
+}
+
+impl From<GetDelayStatistics::DelayStatisticsItem> for DelayStatisticsItem {
+    fn from(item: GetDelayStatistics::DelayStatisticsItem) -> Self {
+        DelayStatisticsItem {
+            begin: item.begin(),
+            end: item.end(),
+            count: item.count(),
+            proportion: item.proportion(),
+            cumulative_ratio: item.cumulativeRatio(),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct DelayStatistics {
+    pub qot_push_type: QotPushType,
+    pub item_list: Vec<DelayStatisticsItem>,
+    pub delay_avg: f32,
+    pub count: i32,
+}
+
+impl DelayStatistics {
+    /// Estimates the `p`th percentile (0-100) in milliseconds, interpolating inside a segment.
+    ///
+    /// The open ended last segment answers its lower bound.
+    pub fn percentile(&self, p: f64) -> Option<f64> {
+        let total: i64 = self.item_list.iter().map(|item| item.count as i64).sum();
+        if total == 0 {
+            return None;
+        }
+
+        let target = p.clamp(0.0, 100.0) / 100.0 * total as f64;
+        let mut cumulative = 0.0;
+        for item in self.item_list.iter().filter(|item| item.count > 0) {
+            let next = cumulative + item.count as f64;
+            if target <= next {
+                if item.end < 0 {
+                    return Some(item.begin as f64);
+                }
+
+                let ratio = (target - cumulative) / item.count as f64;
+                return Some(item.begin as f64 + (item.end - item.begin) as f64 * ratio);
+            }
+            cumulative = next;
+        }
+
+        self.item_list.last().map(|item| item.begin as f64)
+    }
+}
+
+impl fmt::Display for DelayStatistics {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{:?}: count={} avg={:.2}ms",
+            self.qot_push_type, self.count, self.delay_avg
+        )?;
+        for p in [50.0, 90.0, 99.0] {
+            match self.percentile(p) {
+                Some(value) => write!(f, " p{}={:.2}ms", p, value)?,
+                None => write!(f, " p{}=-", p)?,
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl From<GetDelayStatistics::DelayStatistics> for DelayStatistics {
+    fn from(delay_statistics: GetDelayStatistics::DelayStatistics) -> Self {
+        let mut item_list = Vec::new();
+        for item in delay_statistics.itemList.iter().cloned() {
+            item_list.push(item.into());
+        }
+
+        DelayStatistics {
+            qot_push_type: QotPushType::from_i32(delay_statistics.qotPushType()).unwrap(),
+            item_list,
+            delay_avg: delay_statistics.delayAvg(),
+            count: delay_statistics.count(),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ReqReplyStatisticsItem {
+    pub proto_id: i32,
+    pub count: i32,
+    pub total_cost_avg: f32, // 平均总耗时，毫秒单位
+    pub open_d_cost_avg: f32,
+    pub net_delay_avg: f32,
+    pub is_local_reply: bool,
+}
+
+impl From<GetDelayStatistics::ReqReplyStatisticsItem> for ReqReplyStatisticsItem {
+    fn from(item: GetDelayStatistics::ReqReplyStatisticsItem) -> Self {
+        ReqReplyStatisticsItem {
+            proto_id: item.protoID(),
+            count: item.count(),
+            total_cost_avg: item.totalCostAvg(),
+            open_d_cost_avg: item.openDCostAvg(),
+            net_delay_avg: item.netDelayAvg(),
+            is_local_reply: item.isLocalReply(),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct PlaceOrderStatisticsItem {
+    pub order_id: String,
+    pub total_cost: f32, // 总耗时，毫秒单位
+    pub open_d_cost: f32,
+    pub net_delay: f32,
+    pub update_cost: f32,
+}
+
+impl From<GetDelayStatistics::PlaceOrderStatisticsItem> for PlaceOrderStatisticsItem {
+    fn from(item: GetDelayStatistics::PlaceOrderStatisticsItem) -> Self {
+        PlaceOrderStatisticsItem {
+            order_id: item.orderID().into(),
+            total_cost: item.totalCost(),
+            open_d_cost: item.openDCost(),
+            net_delay: item.netDelay(),
+            update_cost: item.updateCost(),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct GetDelayStatisticsResponse {
+    pub qot_push_statistics_list: Vec<DelayStatistics>,
+    pub req_reply_statistics_list: Vec<ReqReplyStatisticsItem>,
+    pub place_order_statistics_list: Vec<PlaceOrderStatisticsItem>,
+}
+
+impl From<Response> for GetDelayStatisticsResponse {
+    fn from(resp: Response) -> Self {
+        let mut qot_push_statistics_list = Vec::new();
+        for delay_statistics in resp.s2c.qotPushStatisticsList.iter().cloned() {
+            qot_push_statistics_list.push(delay_statistics.into());
+        }
+
+        let mut req_reply_statistics_list = Vec::new();
+        for item in resp.s2c.reqReplyStatisticsList.iter().cloned() {
+            req_reply_statistics_list.push(item.into());
+        }
+
+        let mut place_order_statistics_list = Vec::new();
+        for item in resp.s2c.placeOrderStatisticsList.iter().cloned() {
+            place_order_statistics_list.push(item.into());
+        }
+
+        GetDelayStatisticsResponse {
+            qot_push_statistics_list,
+            req_reply_statistics_list,
+            place_order_statistics_list,
+        }
+    }
+}
+
+impl FutuRequest for GetDelayStatisticsRequest {
+    const PROTO_ID: u32 = PROTO_ID;
+
+    type Request = Request;
+    type Response = Response;
+    type Output = GetDelayStatisticsResponse;
+}
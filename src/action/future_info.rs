@@ -0,0 +1,146 @@
+use super::common::{Security, SecurityVec};
+use crate::{
+    FutuRequest,
+    Qot_GetFutureInfo::{self, Request, Response, C2S},
+};
+use chrono::NaiveDate;
+use protobuf::MessageField;
+
+const PROTO_ID: u32 = 3218;
+
+#[derive(Debug)]
+pub struct GetFutureInfoRequest(Vec<Security>);
+
+impl Into<Request> for GetFutureInfoRequest {
+    fn into(self) -> Request {
+        let mut req = Request::new();
+        let mut c2s = C2S::new();
+        c2s.securityList = SecurityVec(self.0).into();
+        req.c2s = MessageField::some(c2s);
+
+        req
+    }
+}
+
+impl GetFutureInfoRequest {
+    pub fn new(security_list: Vec<Security>) -> Self {
+        GetFutureInfoRequest(security_list)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct TradeTime {
+    pub begin: Option<f64>, // 开始时间,以分钟为单位
+    pub end: Option<f64>,   // 结束时间,以分钟为单位
+}
+
+#[derive(Debug, Clone)]
+pub struct FutureInfo {
+    pub name: String,
+    pub security: Security,
+    pub last_trade_time: String, // 最后交易日，只有非主连期货合约才有该字段
+    pub last_trade_timestamp: Option<f64>,
+    pub owner: Option<Security>, // 标的股 股票期货和股指期货才有该字段
+    pub owner_other: String,
+    pub exchange: String,
+    pub contract_type: String,
+    pub contract_size: f64, // 合约规模
+    pub contract_size_unit: String,
+    pub quote_currency: String,
+    pub min_var: f64, // 最小变动单位
+    pub min_var_unit: String,
+    pub quote_unit: Option<String>,
+    pub trade_time: Vec<TradeTime>,
+    pub time_zone: String,
+    pub exchange_format_url: String,
+    pub origin: Option<Security>, // 实际合约代码
+}
+
+impl FutureInfo {
+    pub fn last_trade_date(&self) -> Option<NaiveDate> {
+        NaiveDate::parse_from_str(self.last_trade_time.get(..10)?, "%Y-%m-%d").ok()
+    }
+
+    /// Days from `today` until the last trade date, `None` for main contracts.
+    pub fn days_until_last_trade(&self, today: NaiveDate) -> Option<i64> {
+        self.last_trade_date()
+            .map(|last_trade_date| (last_trade_date - today).num_days())
+    }
+}
+
+impl From<Qot_GetFutureInfo::FutureInfo> for FutureInfo {
+    fn from(future_info: Qot_GetFutureInfo::FutureInfo) -> Self {
+        let mut trade_time = Vec::new();
+        for time in &future_info.tradeTime {
+            trade_time.push(TradeTime {
+                begin: time.begin,
+                end: time.end,
+            });
+        }
+
+        FutureInfo {
+            name: future_info.name().into(),
+            security: future_info.security.to_owned().unwrap().into(),
+            last_trade_time: future_info.lastTradeTime().into(),
+            last_trade_timestamp: future_info.lastTradeTimestamp,
+            owner: future_info
+                .owner
+                .to_owned()
+                .into_option()
+                .map(|owner| owner.into()),
+            owner_other: future_info.ownerOther().into(),
+            exchange: future_info.exchange().into(),
+            contract_type: future_info.contractType().into(),
+            contract_size: future_info.contractSize(),
+            contract_size_unit: future_info.contractSizeUnit().into(),
+            quote_currency: future_info.quoteCurrency().into(),
+            min_var: future_info.minVar(),
+            min_var_unit: future_info.minVarUnit().into(),
+            quote_unit: future_info.quoteUnit.to_owned(),
+            trade_time,
+            time_zone: future_info.timeZone().into(),
+            exchange_format_url: future_info.exchangeFormatUrl().into(),
+            origin: future_info
+                .origin
+                .to_owned()
+                .into_option()
+                .map(|origin| origin.into()),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct GetFutureInfoResponse(pub Vec<FutureInfo>);
+
+impl From<Response> for GetFutureInfoResponse {
+    fn from(resp: Response) -> Self {
+        let mut future_info_list = Vec::new();
+        for future_info in resp.s2c.futureInfoList.iter().cloned() {
+            future_info_list.push(future_info.into());
+        }
+
+        GetFutureInfoResponse(future_info_list)
+    }
+}
+
+impl GetFutureInfoResponse {
+    pub fn into_inner(self) -> Vec<FutureInfo> {
+        self.0
+    }
+}
+
+/// The actual contract a main (continuous) future currently maps to.
+#[derive(Debug)]
+pub struct MainContract {
+    pub main: Security,
+    pub contract: FutureInfo,
+    pub days_until_last_trade: Option<i64>,
+}
+
+impl FutuRequest for GetFutureInfoRequest {
+    const PROTO_ID: u32 = PROTO_ID;
+
+    type Request = Request;
+    type Response = Response;
+    type Output = GetFutureInfoResponse;
+}
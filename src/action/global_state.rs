@@ -1,7 +1,6 @@
 use crate::{
     action::common::ProgramStatus,
-    Common::RetType,
-    Frame,
+    FutuRequest,
     GetGlobalState::{Request, Response, C2S},
     Qot_Common::QotMarketState,
 };
@@ -22,12 +21,6 @@ impl Into<Request> for GetGlobalStateRequest {
     }
 }
 
-impl GetGlobalStateRequest {
-    pub fn into_frame(self) -> Frame<Request> {
-        Frame::new(self.into(), PROTO_ID)
-    }
-}
-
 #[derive(Debug)]
 pub struct GetGlobalStateResponse {
     pub market_hk: QotMarketState,
@@ -91,10 +84,10 @@ impl From<Response> for GetGlobalStateResponse {
     }
 }
 
-pub fn check_response(resp: Response) -> crate::Result<GetGlobalStateResponse> {
-    if resp.retType() == RetType::RetType_Succeed as i32 {
-        return Ok(resp.into());
-    }
+impl FutuRequest for GetGlobalStateRequest {
+    const PROTO_ID: u32 = PROTO_ID;
 
-    Err(resp.retMsg().into())
+    type Request = Request;
+    type Response = Response;
+    type Output = GetGlobalStateResponse;
 }
@@ -0,0 +1,150 @@
+use super::common::{KLine, Security, SecurityVec};
+use crate::{
+    FutuRequest,
+    Qot_Common::{KLType, RehabType},
+    Qot_GetHistoryKLPoints::{DataStatus, NoDataMode, Request, Response, C2S},
+};
+use protobuf::{Enum, MessageField};
+use std::collections::HashMap;
+
+const PROTO_ID: u32 = 3101;
+
+#[derive(Debug, Clone)]
+pub struct GetHistoryKLPointsRequest {
+    rehab_type: RehabType,
+    kl_type: KLType,
+    no_data_mode: NoDataMode,
+    security_list: Vec<Security>,
+    time_list: Vec<String>,
+    max_req_security_num: Option<i32>,
+    need_kl_fields_flag: Option<i64>,
+}
+
+impl Into<Request> for GetHistoryKLPointsRequest {
+    fn into(self) -> Request {
+        let mut req = Request::new();
+        let mut c2s = C2S::new();
+        c2s.set_rehabType(self.rehab_type as i32);
+        c2s.set_klType(self.kl_type as i32);
+        c2s.set_noDataMode(self.no_data_mode as i32);
+        c2s.securityList = SecurityVec(self.security_list).into();
+        c2s.timeList = self.time_list;
+        c2s.maxReqSecurityNum = self.max_req_security_num;
+        c2s.needKLFieldsFlag = self.need_kl_fields_flag;
+        req.c2s = MessageField::some(c2s);
+
+        req
+    }
+}
+
+impl GetHistoryKLPointsRequest {
+    pub fn new(
+        rehab_type: RehabType,
+        kl_type: KLType,
+        no_data_mode: NoDataMode,
+        security_list: Vec<Security>,
+        time_list: Vec<String>,
+        max_req_security_num: Option<i32>,
+        need_kl_fields_flag: Option<i64>,
+    ) -> Self {
+        GetHistoryKLPointsRequest {
+            rehab_type,
+            kl_type,
+            no_data_mode,
+            security_list,
+            time_list,
+            max_req_security_num,
+            need_kl_fields_flag,
+        }
+    }
+
+    /// Splits the request so that none asks for more than `max_req_security_num` securities.
+    pub(crate) fn into_chunks(self) -> Vec<GetHistoryKLPointsRequest> {
+        let size = match self.max_req_security_num {
+            Some(n) if n > 0 && self.security_list.len() > n as usize => n as usize,
+            _ => return vec![self],
+        };
+
+        self.security_list
+            .chunks(size)
+            .map(|security_list| GetHistoryKLPointsRequest {
+                security_list: security_list.to_vec(),
+                ..self.clone()
+            })
+            .collect()
+    }
+
+    /// The request for the securities missing from `resp`, if any.
+    ///
+    /// None as well when `resp` brought none of them, asking again would not get further.
+    pub(crate) fn remaining(&self, resp: &GetHistoryKLPointsResponse) -> Option<Self> {
+        let security_list: Vec<Security> = self
+            .security_list
+            .iter()
+            .filter(|security| !resp.kl_points.contains_key(security))
+            .cloned()
+            .collect();
+        if security_list.is_empty() || security_list.len() == self.security_list.len() {
+            return None;
+        }
+
+        Some(GetHistoryKLPointsRequest {
+            security_list,
+            ..self.clone()
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct KLPoint {
+    pub time: String,       // 请求的时间
+    pub status: DataStatus, // 数据状态，请求的时间点或其前后的时间点
+    pub kline: KLine,
+}
+
+#[derive(Debug, Default)]
+pub struct GetHistoryKLPointsResponse {
+    /// The kline of each requested time point, keyed by security.
+    pub kl_points: HashMap<Security, Vec<KLPoint>>,
+    /// Whether securities were left out, by any of the chunks when the request was split.
+    pub has_next: bool,
+}
+
+impl From<Response> for GetHistoryKLPointsResponse {
+    fn from(resp: Response) -> Self {
+        let mut kl_points = HashMap::new();
+        for security_kl_points in resp.s2c.klPointList.iter().cloned() {
+            let mut kl_list = Vec::new();
+            for point in security_kl_points.klList {
+                kl_list.push(KLPoint {
+                    time: point.reqTime().to_string(),
+                    status: DataStatus::from_i32(point.status())
+                        .unwrap_or(DataStatus::DataStatus_Null),
+                    kline: point.kl.unwrap().into(),
+                });
+            }
+
+            kl_points.insert(security_kl_points.security.unwrap().into(), kl_list);
+        }
+
+        GetHistoryKLPointsResponse {
+            kl_points,
+            has_next: resp.s2c.hasNext(),
+        }
+    }
+}
+
+impl GetHistoryKLPointsResponse {
+    pub(crate) fn merge(&mut self, other: GetHistoryKLPointsResponse) {
+        self.kl_points.extend(other.kl_points);
+        self.has_next |= other.has_next;
+    }
+}
+
+impl FutuRequest for GetHistoryKLPointsRequest {
+    const PROTO_ID: u32 = PROTO_ID;
+
+    type Request = Request;
+    type Response = Response;
+    type Output = GetHistoryKLPointsResponse;
+}
@@ -1,7 +1,6 @@
 use super::common::{Order, TrdFilterConditions, TrdHeader};
 use crate::{
-    Common::RetType,
-    Frame,
+    FutuRequest,
     Trd_Common::OrderStatus,
     Trd_GetHistoryOrderList::{Request, Response, C2S},
 };
@@ -55,12 +54,6 @@ impl Into<Request> for GetHistoryOrderListRequest {
     }
 }
 
-impl GetHistoryOrderListRequest {
-    pub fn into_frame(self) -> Frame<Request> {
-        Frame::new(self.into(), PROTO_ID)
-    }
-}
-
 #[derive(Debug)]
 pub struct GetHistoryOrderListResponse {
     pub order_list: Vec<Order>,
@@ -77,10 +70,10 @@ impl From<Response> for GetHistoryOrderListResponse {
     }
 }
 
-pub fn check_response(resp: Response) -> crate::Result<GetHistoryOrderListResponse> {
-    if resp.retType() == RetType::RetType_Succeed as i32 {
-        return Ok(resp.into());
-    }
+impl FutuRequest for GetHistoryOrderListRequest {
+    const PROTO_ID: u32 = PROTO_ID;
 
-    Err(resp.retMsg().into())
+    type Request = Request;
+    type Response = Response;
+    type Output = GetHistoryOrderListResponse;
 }
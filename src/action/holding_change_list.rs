@@ -0,0 +1,76 @@
+use super::common::{Security, ShareHoldingChange};
+use crate::{
+    FutuRequest,
+    Qot_Common::HolderCategory,
+    Qot_GetHoldingChangeList::{Request, Response, C2S},
+};
+use protobuf::MessageField;
+
+const PROTO_ID: u32 = 3208;
+
+/// `begin_time` and `end_time` filter on publish time, formatted as "YYYY-MM-DD HH:MM:SS".
+#[derive(Debug)]
+pub struct GetHoldingChangeListRequest {
+    security: Security,
+    holder_category: HolderCategory,
+    begin_time: Option<String>,
+    end_time: Option<String>,
+}
+
+impl Into<Request> for GetHoldingChangeListRequest {
+    fn into(self) -> Request {
+        let mut req = Request::new();
+        let mut c2s = C2S::new();
+        c2s.security = MessageField::some(self.security.into());
+        c2s.set_holderCategory(self.holder_category as i32);
+        c2s.beginTime = self.begin_time;
+        c2s.endTime = self.end_time;
+        req.c2s = MessageField::some(c2s);
+
+        req
+    }
+}
+
+impl GetHoldingChangeListRequest {
+    pub fn new(
+        security: Security,
+        holder_category: HolderCategory,
+        begin_time: Option<String>,
+        end_time: Option<String>,
+    ) -> Self {
+        GetHoldingChangeListRequest {
+            security,
+            holder_category,
+            begin_time,
+            end_time,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct GetHoldingChangeListResponse {
+    pub security: Security,
+    pub holding_change_list: Vec<ShareHoldingChange>,
+}
+
+impl From<Response> for GetHoldingChangeListResponse {
+    fn from(resp: Response) -> Self {
+        let mut holding_change_list = Vec::new();
+        for holding_change in resp.s2c.holdingChangeList.iter().cloned() {
+            holding_change_list.push(holding_change.into());
+        }
+
+        GetHoldingChangeListResponse {
+            security: resp.s2c.security.to_owned().unwrap().into(),
+            holding_change_list,
+        }
+    }
+}
+
+impl FutuRequest for GetHoldingChangeListRequest {
+    const PROTO_ID: u32 = PROTO_ID;
+
+    type Request = Request;
+    type Response = Response;
+    type Output = GetHoldingChangeListResponse;
+}
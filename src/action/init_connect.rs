@@ -1,6 +1,6 @@
 use crate::{
-    Common::RetType,
-    Frame,
+    Common::ProtoFmt,
+    FutuRequest,
     InitConnect::{Request, Response, C2S},
 };
 use protobuf::MessageField;
@@ -13,6 +13,7 @@ pub struct InitConnectRequest {
     client_id: String,
     recv_notify: bool,
     package_enc_algo: i32,
+    push_proto_fmt: Option<ProtoFmt>,
     programming_language: String,
 }
 
@@ -24,6 +25,9 @@ impl Into<Request> for InitConnectRequest {
         c2s.set_clientID(self.client_id);
         c2s.set_recvNotify(self.recv_notify);
         c2s.set_packetEncAlgo(self.package_enc_algo);
+        if let Some(push_proto_fmt) = self.push_proto_fmt {
+            c2s.set_pushProtoFmt(push_proto_fmt as i32);
+        }
         c2s.set_programmingLanguage(self.programming_language);
         req.c2s = MessageField::some(c2s);
 
@@ -32,18 +36,18 @@ impl Into<Request> for InitConnectRequest {
 }
 
 impl InitConnectRequest {
-    pub fn into_frame(self) -> Frame<Request> {
-        Frame::new(self.into(), PROTO_ID)
-    }
-}
-
-impl Default for InitConnectRequest {
-    fn default() -> Self {
+    pub fn new(
+        client_ver: i32,
+        client_id: String,
+        recv_notify: bool,
+        push_proto_fmt: Option<ProtoFmt>,
+    ) -> Self {
         InitConnectRequest {
-            client_ver: 221,
-            client_id: "800".into(),
-            recv_notify: false,
+            client_ver,
+            client_id,
+            recv_notify,
             package_enc_algo: -1,
+            push_proto_fmt,
             programming_language: "Rust".into(),
         }
     }
@@ -74,10 +78,10 @@ impl From<Response> for InitConnectResponse {
     }
 }
 
-pub fn check_response(resp: Response) -> crate::Result<InitConnectResponse> {
-    if resp.retType() == RetType::RetType_Succeed as i32 {
-        return Ok(InitConnectResponse::from(resp));
-    }
+impl FutuRequest for InitConnectRequest {
+    const PROTO_ID: u32 = PROTO_ID;
 
-    Err(resp.retMsg().into())
+    type Request = Request;
+    type Response = Response;
+    type Output = InitConnectResponse;
 }
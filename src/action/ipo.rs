@@ -1,7 +1,6 @@
 use super::common::Security;
 use crate::{
-    Common::RetType,
-    Frame,
+    FutuRequest,
     Qot_Common::QotMarket,
     Qot_GetIpoList::{Request, Response, C2S},
 };
@@ -28,10 +27,6 @@ impl GetIpoListRequest {
         GetIpoListRequest(market)
     }
 
-    pub fn into_frame(self) -> Frame<Request> {
-        Frame::new(self.into(), PROTO_ID)
-    }
-
     pub fn into_inner(self) -> QotMarket {
         self.0
     }
@@ -73,10 +68,10 @@ impl From<Response> for GetIpoListResponse {
     }
 }
 
-pub fn check_response(resp: Response) -> crate::Result<GetIpoListResponse> {
-    if resp.retType() == RetType::RetType_Succeed as i32 {
-        return Ok(resp.into());
-    }
+impl FutuRequest for GetIpoListRequest {
+    const PROTO_ID: u32 = PROTO_ID;
 
-    Err(resp.retMsg().into())
+    type Request = Request;
+    type Response = Response;
+    type Output = GetIpoListResponse;
 }
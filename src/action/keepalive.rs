@@ -1,6 +1,5 @@
 use crate::{
-    Common::RetType,
-    Frame,
+    FutuRequest,
     KeepAlive::{Request, Response, C2S},
 };
 use protobuf::MessageField;
@@ -25,10 +24,6 @@ impl KeepAliveRequest {
     pub fn new(time: i64) -> Self {
         KeepAliveRequest(time)
     }
-
-    pub fn into_frame(self) -> Frame<Request> {
-        Frame::new(self.into(), PROTO_ID)
-    }
 }
 
 #[derive(Debug)]
@@ -40,10 +35,10 @@ impl From<Response> for KeepAliveResponse {
     }
 }
 
-pub fn check_response(resp: Response) -> crate::Result<KeepAliveResponse> {
-    if resp.retType() == RetType::RetType_Succeed as i32 {
-        return Ok(resp.into());
-    }
+impl FutuRequest for KeepAliveRequest {
+    const PROTO_ID: u32 = PROTO_ID;
 
-    Err(resp.retMsg().into())
+    type Request = Request;
+    type Response = Response;
+    type Output = KeepAliveResponse;
 }
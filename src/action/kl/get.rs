@@ -0,0 +1,96 @@
+use super::super::common::{KLine, Security};
+use crate::{
+    FutuRequest,
+    Qot_Common::{KLType, RehabType, SubType},
+    Qot_GetKL::{Request, Response, C2S},
+};
+use protobuf::MessageField;
+
+pub const PROTO_ID: u32 = 3006;
+
+#[derive(Debug)]
+pub struct GetKLRequest {
+    rehab_type: RehabType,
+    kl_type: KLType,
+    security: Security,
+    req_num: i32,
+}
+
+impl Into<Request> for GetKLRequest {
+    fn into(self) -> Request {
+        let mut req = Request::new();
+        let mut c2s = C2S::new();
+        c2s.set_rehabType(self.rehab_type as i32);
+        c2s.set_klType(self.kl_type as i32);
+        c2s.security = MessageField::some(self.security.into());
+        c2s.set_reqNum(self.req_num);
+        req.c2s = MessageField::some(c2s);
+
+        req
+    }
+}
+
+impl GetKLRequest {
+    pub fn new(security: Security, kl_type: KLType, rehab_type: RehabType, req_num: i32) -> Self {
+        GetKLRequest {
+            rehab_type,
+            kl_type,
+            security,
+            req_num,
+        }
+    }
+
+    pub fn security(&self) -> &Security {
+        &self.security
+    }
+
+    /// The subscription OpenD requires before serving this request.
+    pub fn sub_type(&self) -> SubType {
+        sub_type(self.kl_type)
+    }
+}
+
+pub fn sub_type(kl_type: KLType) -> SubType {
+    match kl_type {
+        KLType::KLType_1Min => SubType::SubType_KL_1Min,
+        KLType::KLType_3Min => SubType::SubType_KL_3Min,
+        KLType::KLType_5Min => SubType::SubType_KL_5Min,
+        KLType::KLType_15Min => SubType::SubType_KL_15Min,
+        KLType::KLType_30Min => SubType::SubType_KL_30Min,
+        KLType::KLType_60Min => SubType::SubType_KL_60Min,
+        KLType::KLType_Day => SubType::SubType_KL_Day,
+        KLType::KLType_Week => SubType::SubType_KL_Week,
+        KLType::KLType_Month => SubType::SubType_KL_Month,
+        KLType::KLType_Quarter => SubType::SubType_KL_Qurater,
+        KLType::KLType_Year => SubType::SubType_KL_Year,
+        KLType::KLType_Unknown => SubType::SubType_None,
+    }
+}
+
+#[derive(Debug)]
+pub struct GetKLResponse {
+    pub security: Security,
+    pub kl_list: Vec<KLine>,
+}
+
+impl From<Response> for GetKLResponse {
+    fn from(resp: Response) -> Self {
+        let mut kl_list = Vec::new();
+        for kl in resp.s2c.klList.iter().cloned() {
+            kl_list.push(kl.into());
+        }
+
+        GetKLResponse {
+            security: resp.s2c.security.to_owned().unwrap().into(),
+            kl_list,
+        }
+    }
+}
+
+impl FutuRequest for GetKLRequest {
+    const PROTO_ID: u32 = PROTO_ID;
+
+    type Request = Request;
+    type Response = Response;
+    type Output = GetKLResponse;
+}
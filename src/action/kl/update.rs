@@ -1,5 +1,5 @@
 use super::super::common::{KLine, Security};
-use crate::{Common::RetType, Qot_Common::KLType, Qot_UpdateKL::Response};
+use crate::{Qot_Common::KLType, Qot_UpdateKL::Response};
 use protobuf::Enum;
 
 pub const PROTO_ID: u32 = 3007;
@@ -27,11 +27,3 @@ impl From<Response> for UpdateKLResponse {
         }
     }
 }
-
-pub fn check_response(resp: Response) -> crate::Result<UpdateKLResponse> {
-    if resp.retType() == RetType::RetType_Succeed as i32 {
-        return Ok(resp.into());
-    }
-
-    Err(resp.retMsg().into())
-}
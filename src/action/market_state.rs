@@ -0,0 +1,74 @@
+use super::common::{Security, SecurityVec};
+use crate::{
+    FutuRequest,
+    Qot_Common::QotMarketState,
+    Qot_GetMarketState::{self, Request, Response, C2S},
+};
+use protobuf::{Enum, MessageField};
+
+const PROTO_ID: u32 = 3223;
+
+#[derive(Debug)]
+pub struct GetMarketStateRequest(Vec<Security>);
+
+impl Into<Request> for GetMarketStateRequest {
+    fn into(self) -> Request {
+        let mut req = Request::new();
+        let mut c2s = C2S::new();
+        c2s.securityList = SecurityVec(self.0).into();
+        req.c2s = MessageField::some(c2s);
+
+        req
+    }
+}
+
+impl GetMarketStateRequest {
+    pub fn new(security_list: Vec<Security>) -> Self {
+        GetMarketStateRequest(security_list)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct MarketInfo {
+    pub security: Security,
+    pub name: String,
+    pub market_state: QotMarketState,
+}
+
+impl From<Qot_GetMarketState::MarketInfo> for MarketInfo {
+    fn from(market_info: Qot_GetMarketState::MarketInfo) -> Self {
+        MarketInfo {
+            security: market_info.security.to_owned().unwrap().into(),
+            name: market_info.name().into(),
+            market_state: QotMarketState::from_i32(market_info.marketState()).unwrap(),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct GetMarketStateResponse(pub Vec<MarketInfo>);
+
+impl From<Response> for GetMarketStateResponse {
+    fn from(resp: Response) -> Self {
+        let mut market_info_list = Vec::new();
+        for market_info in resp.s2c.marketInfoList.iter().cloned() {
+            market_info_list.push(market_info.into());
+        }
+
+        GetMarketStateResponse(market_info_list)
+    }
+}
+
+impl GetMarketStateResponse {
+    pub fn into_inner(self) -> Vec<MarketInfo> {
+        self.0
+    }
+}
+
+impl FutuRequest for GetMarketStateRequest {
+    const PROTO_ID: u32 = PROTO_ID;
+
+    type Request = Request;
+    type Response = Response;
+    type Output = GetMarketStateResponse;
+}
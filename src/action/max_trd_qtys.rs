@@ -1,7 +1,6 @@
 use super::common::{MaxTrdQtys, TrdHeader};
 use crate::{
-    Common::RetType,
-    Frame,
+    FutuRequest,
     Trd_Common::{OrderType, TrdSecMarket},
     Trd_GetMaxTrdQtys::{Request, Response, C2S, S2C},
 };
@@ -61,10 +60,6 @@ impl GetMaxTrdQtysRequest {
             sec_market: Some(sec_market),
         }
     }
-
-    pub fn into_frame(self) -> Frame<Request> {
-        Frame::new(self.into(), PROTO_ID)
-    }
 }
 
 #[derive(Debug)]
@@ -94,10 +89,10 @@ impl From<Response> for GetMaxTrdQtysResponse {
     }
 }
 
-pub fn check_response(resp: Response) -> crate::Result<GetMaxTrdQtysResponse> {
-    if resp.retType() == RetType::RetType_Succeed as i32 {
-        return Ok(resp.into());
-    }
+impl FutuRequest for GetMaxTrdQtysRequest {
+    const PROTO_ID: u32 = PROTO_ID;
 
-    Err(resp.retMsg().into())
+    type Request = Request;
+    type Response = Response;
+    type Output = GetMaxTrdQtysResponse;
 }
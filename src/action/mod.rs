@@ -1,20 +1,39 @@
 pub mod basic_qot;
+pub mod capital_distribution;
+pub mod capital_flow;
+pub mod code_change;
 pub mod common;
+pub mod delay_statistics;
+pub mod future_info;
 pub mod global_state;
+pub mod history_kl_points;
 pub mod history_order_list;
+pub mod holding_change_list;
 pub mod init_connect;
 pub mod ipo;
 pub mod keepalive;
 pub mod kl;
+pub mod market_state;
 pub mod max_trd_qtys;
 pub mod order;
+pub mod owner_plate;
 pub mod plate_security;
+pub mod plate_set;
 pub mod position_list;
 pub mod price_reminder;
+pub mod reference;
+pub mod reg_qot_push;
+pub mod rehab;
 pub mod rt;
 pub mod security_snapshot;
+pub mod static_info;
 pub mod stock_filter;
+pub mod sub_info;
 pub mod subscribe;
+pub mod suspend;
+pub mod trade_date;
 pub mod unlock;
+pub mod user_info;
 pub mod user_security;
 pub mod user_security_group;
+pub mod verification;
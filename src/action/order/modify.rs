@@ -1,7 +1,6 @@
 use super::super::common::{PacketID, TrdHeader};
 use crate::{
-    Common::RetType,
-    Frame,
+    FutuRequest,
     Trd_Common::ModifyOrderOp,
     Trd_ModifyOrder::{Request, Response, C2S},
 };
@@ -9,7 +8,7 @@ use protobuf::MessageField;
 
 const PROTO_ID: u32 = 2205;
 
-#[derive(Debug, Default)]
+#[derive(Debug, Default, Clone)]
 pub struct ModifyOrderRequest {
     pub packet_id: PacketID,
     pub header: TrdHeader,
@@ -53,10 +52,6 @@ impl ModifyOrderRequest {
             price,
         }
     }
-
-    pub fn into_frame(self) -> Frame<Request> {
-        Frame::new(self.into(), PROTO_ID)
-    }
 }
 
 #[derive(Debug)]
@@ -72,10 +67,10 @@ impl From<Response> for ModifyOrderResponse {
     }
 }
 
-pub fn check_response(resp: Response) -> crate::Result<ModifyOrderResponse> {
-    if resp.retType() == RetType::RetType_Succeed as i32 {
-        return Ok(resp.into());
-    }
+impl FutuRequest for ModifyOrderRequest {
+    const PROTO_ID: u32 = PROTO_ID;
 
-    Err(resp.retMsg().into())
+    type Request = Request;
+    type Response = Response;
+    type Output = ModifyOrderResponse;
 }
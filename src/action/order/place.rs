@@ -1,7 +1,6 @@
 use super::super::common::{PacketID, TrdHeader};
 use crate::{
-    Common::RetType,
-    Frame,
+    FutuRequest,
     Trd_Common::{OrderType, TimeInForce, TrailType, TrdSecMarket, TrdSide},
     Trd_PlaceOrder::{Request, Response, C2S},
 };
@@ -9,7 +8,7 @@ use protobuf::MessageField;
 
 const PROTO_ID: u32 = 2202;
 
-#[derive(Debug, Default)]
+#[derive(Debug, Default, Clone)]
 pub struct PlaceOrderRequest {
     pub packet_id: PacketID,
     pub header: TrdHeader,
@@ -63,12 +62,6 @@ impl Into<Request> for PlaceOrderRequest {
     }
 }
 
-impl PlaceOrderRequest {
-    pub fn into_frame(self) -> Frame<Request> {
-        Frame::new(self.into(), PROTO_ID)
-    }
-}
-
 #[derive(Debug)]
 pub struct PlaceOrderResponse(Option<u64>);
 
@@ -84,10 +77,10 @@ impl PlaceOrderResponse {
     }
 }
 
-pub fn check_response(resp: Response) -> crate::Result<PlaceOrderResponse> {
-    if resp.retType() == RetType::RetType_Succeed as i32 {
-        return Ok(resp.into());
-    }
+impl FutuRequest for PlaceOrderRequest {
+    const PROTO_ID: u32 = PROTO_ID;
 
-    Err(resp.retMsg().into())
+    type Request = Request;
+    type Response = Response;
+    type Output = PlaceOrderResponse;
 }
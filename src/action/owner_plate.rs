@@ -0,0 +1,76 @@
+use super::common::{PlateInfo, Security, SecurityVec};
+use crate::{
+    FutuRequest,
+    Qot_GetOwnerPlate::{self, Request, Response, C2S},
+};
+use protobuf::MessageField;
+
+const PROTO_ID: u32 = 3207;
+
+#[derive(Debug)]
+pub struct GetOwnerPlateRequest(Vec<Security>);
+
+impl Into<Request> for GetOwnerPlateRequest {
+    fn into(self) -> Request {
+        let mut req = Request::new();
+        let mut c2s = C2S::new();
+        c2s.securityList = SecurityVec(self.0).into();
+        req.c2s = MessageField::some(c2s);
+
+        req
+    }
+}
+
+impl GetOwnerPlateRequest {
+    pub fn new(security_list: Vec<Security>) -> Self {
+        GetOwnerPlateRequest(security_list)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct SecurityOwnerPlate {
+    pub security: Security,
+    pub plate_info_list: Vec<PlateInfo>,
+}
+
+impl From<Qot_GetOwnerPlate::SecurityOwnerPlate> for SecurityOwnerPlate {
+    fn from(owner_plate: Qot_GetOwnerPlate::SecurityOwnerPlate) -> Self {
+        let mut plate_info_list = Vec::new();
+        for plate_info in owner_plate.plateInfoList {
+            plate_info_list.push(plate_info.into());
+        }
+
+        SecurityOwnerPlate {
+            security: owner_plate.security.unwrap().into(),
+            plate_info_list,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct GetOwnerPlateResponse(pub Vec<SecurityOwnerPlate>);
+
+impl From<Response> for GetOwnerPlateResponse {
+    fn from(resp: Response) -> Self {
+        let mut owner_plate_list = Vec::new();
+        for owner_plate in resp.s2c.ownerPlateList.iter().cloned() {
+            owner_plate_list.push(owner_plate.into());
+        }
+
+        GetOwnerPlateResponse(owner_plate_list)
+    }
+}
+
+impl GetOwnerPlateResponse {
+    pub fn into_inner(self) -> Vec<SecurityOwnerPlate> {
+        self.0
+    }
+}
+
+impl FutuRequest for GetOwnerPlateRequest {
+    const PROTO_ID: u32 = PROTO_ID;
+
+    type Request = Request;
+    type Response = Response;
+    type Output = GetOwnerPlateResponse;
+}
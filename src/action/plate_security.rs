@@ -1,7 +1,6 @@
 use super::common::{Security, SecurityStaticInfo};
 use crate::{
-    Common::RetType,
-    Frame,
+    FutuRequest,
     Qot_Common::SortField,
     Qot_GetPlateSecurity::{Request, Response, C2S},
 };
@@ -41,10 +40,6 @@ impl GetPlateSecurityRequest {
             ascend,
         }
     }
-
-    pub fn into_frame(self) -> Frame<Request> {
-        Frame::new(self.into(), PROTO_ID)
-    }
 }
 
 #[derive(Debug)]
@@ -55,20 +50,18 @@ pub struct GetPlateSecurityResponse {
 impl From<Response> for GetPlateSecurityResponse {
     fn from(resp: Response) -> Self {
         let mut static_info_list = Vec::new();
-        for static_info in &resp.s2c.staticInfoList {
-            static_info_list.push(SecurityStaticInfo {
-                basic: static_info.basic.to_owned().unwrap().into(),
-            });
+        for static_info in resp.s2c.staticInfoList.iter().cloned() {
+            static_info_list.push(static_info.into());
         }
 
         GetPlateSecurityResponse { static_info_list }
     }
 }
 
-pub fn check_response(resp: Response) -> crate::Result<GetPlateSecurityResponse> {
-    if resp.retType() == RetType::RetType_Succeed as i32 {
-        return Ok(resp.into());
-    }
+impl FutuRequest for GetPlateSecurityRequest {
+    const PROTO_ID: u32 = PROTO_ID;
 
-    Err(resp.retMsg().into())
+    type Request = Request;
+    type Response = Response;
+    type Output = GetPlateSecurityResponse;
 }
@@ -0,0 +1,64 @@
+use super::common::PlateInfo;
+use crate::{
+    FutuRequest,
+    Qot_Common::{PlateSetType, QotMarket},
+    Qot_GetPlateSet::{Request, Response, C2S},
+};
+use protobuf::MessageField;
+
+const PROTO_ID: u32 = 3204;
+
+#[derive(Debug)]
+pub struct GetPlateSetRequest {
+    market: QotMarket,
+    plate_set_type: PlateSetType,
+}
+
+impl Into<Request> for GetPlateSetRequest {
+    fn into(self) -> Request {
+        let mut req = Request::new();
+        let mut c2s = C2S::new();
+        c2s.set_market(self.market as i32);
+        c2s.set_plateSetType(self.plate_set_type as i32);
+        req.c2s = MessageField::some(c2s);
+
+        req
+    }
+}
+
+impl GetPlateSetRequest {
+    pub fn new(market: QotMarket, plate_set_type: PlateSetType) -> Self {
+        GetPlateSetRequest {
+            market,
+            plate_set_type,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct GetPlateSetResponse(pub Vec<PlateInfo>);
+
+impl From<Response> for GetPlateSetResponse {
+    fn from(resp: Response) -> Self {
+        let mut plate_info_list = Vec::new();
+        for plate_info in resp.s2c.plateInfoList.iter().cloned() {
+            plate_info_list.push(plate_info.into());
+        }
+
+        GetPlateSetResponse(plate_info_list)
+    }
+}
+
+impl GetPlateSetResponse {
+    pub fn into_inner(self) -> Vec<PlateInfo> {
+        self.0
+    }
+}
+
+impl FutuRequest for GetPlateSetRequest {
+    const PROTO_ID: u32 = PROTO_ID;
+
+    type Request = Request;
+    type Response = Response;
+    type Output = GetPlateSetResponse;
+}
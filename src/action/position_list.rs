@@ -1,7 +1,6 @@
 use super::common::{Position, TrdHeader};
 use crate::{
-    Common::RetType,
-    Frame,
+    FutuRequest,
     Trd_GetPositionList::{Request, Response, C2S},
 };
 use protobuf::MessageField;
@@ -33,10 +32,6 @@ impl GetPositionListRequest {
             refresh_cache,
         }
     }
-
-    pub fn into_frame(self) -> Frame<Request> {
-        Frame::new(self.into(), PROTO_ID)
-    }
 }
 
 #[derive(Debug)]
@@ -56,10 +51,10 @@ impl From<Response> for GetPositionListResponse {
     }
 }
 
-pub fn check_response(resp: Response) -> crate::Result<GetPositionListResponse> {
-    if resp.retType() == RetType::RetType_Succeed as i32 {
-        return Ok(resp.into());
-    }
+impl FutuRequest for GetPositionListRequest {
+    const PROTO_ID: u32 = PROTO_ID;
 
-    Err(resp.retMsg().into())
+    type Request = Request;
+    type Response = Response;
+    type Output = GetPositionListResponse;
 }
@@ -1,7 +1,6 @@
 use super::super::common::Security;
 use crate::{
-    Common::RetType,
-    Frame,
+    FutuRequest,
     Qot_Common::{PriceReminderFreq, PriceReminderType, QotMarket},
     Qot_GetPriceReminder::{self, Request, Response, C2S},
 };
@@ -37,10 +36,6 @@ impl GetPriceReminderRequest {
     pub fn new(security: Option<Security>, market: Option<QotMarket>) -> Self {
         GetPriceReminderRequest { security, market }
     }
-
-    pub fn into_frame(self) -> Frame<Request> {
-        Frame::new(self.into(), PROTO_ID)
-    }
 }
 
 #[derive(Debug)]
@@ -100,10 +95,10 @@ impl From<Response> for GetPriceReminderResponse {
     }
 }
 
-pub fn check_response(resp: Response) -> crate::Result<GetPriceReminderResponse> {
-    if resp.retType() == RetType::RetType_Succeed as i32 {
-        return Ok(resp.into());
-    }
+impl FutuRequest for GetPriceReminderRequest {
+    const PROTO_ID: u32 = PROTO_ID;
 
-    Err(resp.retMsg().into())
+    type Request = Request;
+    type Response = Response;
+    type Output = GetPriceReminderResponse;
 }
@@ -1,7 +1,6 @@
 use super::super::common::Security;
 use crate::{
-    Common::RetType,
-    Frame,
+    FutuRequest,
     Qot_Common::{PriceReminderFreq, PriceReminderType},
     Qot_SetPriceReminder::{Request, Response, SetPriceReminderOp, C2S},
 };
@@ -63,10 +62,6 @@ impl SetPriceReminderRequest {
             note,
         }
     }
-
-    pub fn into_frame(self) -> Frame<Request> {
-        Frame::new(self.into(), PROTO_ID)
-    }
 }
 
 #[derive(Debug)]
@@ -78,10 +73,10 @@ impl From<Response> for SetPriceReminderResponse {
     }
 }
 
-pub fn check_response(resp: Response) -> crate::Result<SetPriceReminderResponse> {
-    if resp.retType() == RetType::RetType_Succeed as i32 {
-        return Ok(resp.into());
-    }
+impl FutuRequest for SetPriceReminderRequest {
+    const PROTO_ID: u32 = PROTO_ID;
 
-    Err(resp.retMsg().into())
+    type Request = Request;
+    type Response = Response;
+    type Output = SetPriceReminderResponse;
 }
@@ -0,0 +1,63 @@
+use super::common::{Security, SecurityStaticInfo};
+use crate::{
+    FutuRequest,
+    Qot_GetReference::{ReferenceType, Request, Response, C2S},
+};
+use protobuf::MessageField;
+
+const PROTO_ID: u32 = 3206;
+
+#[derive(Debug)]
+pub struct GetReferenceRequest {
+    security: Security,
+    reference_type: ReferenceType,
+}
+
+impl Into<Request> for GetReferenceRequest {
+    fn into(self) -> Request {
+        let mut req = Request::new();
+        let mut c2s = C2S::new();
+        c2s.security = MessageField::some(self.security.into());
+        c2s.set_referenceType(self.reference_type as i32);
+        req.c2s = MessageField::some(c2s);
+
+        req
+    }
+}
+
+impl GetReferenceRequest {
+    pub fn new(security: Security, reference_type: ReferenceType) -> Self {
+        GetReferenceRequest {
+            security,
+            reference_type,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct GetReferenceResponse(pub Vec<SecurityStaticInfo>);
+
+impl From<Response> for GetReferenceResponse {
+    fn from(resp: Response) -> Self {
+        let mut static_info_list = Vec::new();
+        for static_info in resp.s2c.staticInfoList.iter().cloned() {
+            static_info_list.push(static_info.into());
+        }
+
+        GetReferenceResponse(static_info_list)
+    }
+}
+
+impl GetReferenceResponse {
+    pub fn into_inner(self) -> Vec<SecurityStaticInfo> {
+        self.0
+    }
+}
+
+impl FutuRequest for GetReferenceRequest {
+    const PROTO_ID: u32 = PROTO_ID;
+
+    type Request = Request;
+    type Response = Response;
+    type Output = GetReferenceResponse;
+}
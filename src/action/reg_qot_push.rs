@@ -0,0 +1,69 @@
+use super::common::{Security, SecurityVec};
+use crate::{
+    request::Ack,
+    FutuRequest,
+    Qot_Common::{RehabType, SubType},
+    Qot_RegQotPush::{Request, Response, C2S},
+};
+use protobuf::MessageField;
+
+pub const PROTO_ID: u32 = 3002;
+
+/// Registers push delivery on the connection without changing the subscription itself.
+#[derive(Debug)]
+pub struct RegQotPushRequest {
+    security_list: Vec<Security>,
+    sub_type_list: Vec<SubType>,
+    rehab_type_list: Vec<RehabType>,
+    is_reg_or_un_reg: bool,
+    is_first_push: Option<bool>,
+}
+
+impl Into<Request> for RegQotPushRequest {
+    fn into(self) -> Request {
+        let mut req = Request::new();
+        let mut c2s = C2S::new();
+        c2s.securityList = SecurityVec(self.security_list).into();
+        c2s.subTypeList = self
+            .sub_type_list
+            .into_iter()
+            .map(|sub_type| sub_type as i32)
+            .collect();
+        c2s.rehabTypeList = self
+            .rehab_type_list
+            .into_iter()
+            .map(|rehab_type| rehab_type as i32)
+            .collect();
+        c2s.set_isRegOrUnReg(self.is_reg_or_un_reg);
+        c2s.isFirstPush = self.is_first_push;
+        req.c2s = MessageField::some(c2s);
+
+        req
+    }
+}
+
+impl RegQotPushRequest {
+    pub fn new(
+        security_list: Vec<Security>,
+        sub_type_list: Vec<SubType>,
+        rehab_type_list: Vec<RehabType>,
+        is_reg_or_un_reg: bool,
+        is_first_push: Option<bool>,
+    ) -> Self {
+        RegQotPushRequest {
+            security_list,
+            sub_type_list,
+            rehab_type_list,
+            is_reg_or_un_reg,
+            is_first_push,
+        }
+    }
+}
+
+impl FutuRequest for RegQotPushRequest {
+    const PROTO_ID: u32 = PROTO_ID;
+
+    type Request = Request;
+    type Response = Response;
+    type Output = Ack;
+}
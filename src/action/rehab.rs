@@ -0,0 +1,201 @@
+use super::common::{KLine, Rehab, Security};
+use crate::{
+    FutuRequest,
+    Qot_RequestRehab::{Request, Response, C2S},
+};
+use protobuf::MessageField;
+
+const PROTO_ID: u32 = 3105;
+
+#[derive(Debug)]
+pub struct RequestRehabRequest(Security);
+
+impl Into<Request> for RequestRehabRequest {
+    fn into(self) -> Request {
+        let mut req = Request::new();
+        let mut c2s = C2S::new();
+        c2s.security = MessageField::some(self.0.into());
+        req.c2s = MessageField::some(c2s);
+
+        req
+    }
+}
+
+impl RequestRehabRequest {
+    pub fn new(security: Security) -> Self {
+        RequestRehabRequest(security)
+    }
+}
+
+#[derive(Debug)]
+pub struct RequestRehabResponse(pub Vec<Rehab>);
+
+impl From<Response> for RequestRehabResponse {
+    fn from(resp: Response) -> Self {
+        let mut rehab_list = Vec::new();
+        for rehab in resp.s2c.rehabList.iter().cloned() {
+            rehab_list.push(rehab.into());
+        }
+
+        RequestRehabResponse(rehab_list)
+    }
+}
+
+impl RequestRehabResponse {
+    pub fn into_inner(self) -> Vec<Rehab> {
+        self.0
+    }
+}
+
+impl FutuRequest for RequestRehabRequest {
+    const PROTO_ID: u32 = PROTO_ID;
+
+    type Request = Request;
+    type Response = Response;
+    type Output = RequestRehabResponse;
+}
+
+// compare on the date part only, rehab time is "yyyy-MM-dd" while kline time may carry a clock
+fn date(time: &str) -> &str {
+    time.get(..10).unwrap_or(time)
+}
+
+fn adjust(price: Option<f64>, factor_a: f64, factor_b: f64) -> Option<f64> {
+    price.map(|price| price * factor_a + factor_b)
+}
+
+fn adjust_kline(kline: &mut KLine, factor_a: f64, factor_b: f64) {
+    kline.high_price = adjust(kline.high_price, factor_a, factor_b);
+    kline.open_price = adjust(kline.open_price, factor_a, factor_b);
+    kline.low_price = adjust(kline.low_price, factor_a, factor_b);
+    kline.close_price = adjust(kline.close_price, factor_a, factor_b);
+}
+
+// the last close belongs to the trading day before the kline, so it may sit on the other
+// side of an ex-date than the kline's own prices
+fn adjust_last_close(kline: &mut KLine, factor_a: f64, factor_b: f64) {
+    kline.last_close_price = adjust(kline.last_close_price, factor_a, factor_b);
+}
+
+/// Converts unadjusted klines to forward adjusted (前复权) prices.
+///
+/// Every rehab event after a kline is applied to it, earliest first. The last close is
+/// adjusted by the events after the day before, including one on the kline's own day.
+pub fn forward_adjust(mut kl_list: Vec<KLine>, rehab_list: &[Rehab]) -> Vec<KLine> {
+    let mut rehab_list: Vec<&Rehab> = rehab_list.iter().collect();
+    rehab_list.sort_by(|a, b| date(&a.time).cmp(date(&b.time)));
+
+    for kline in kl_list.iter_mut() {
+        for rehab in rehab_list.iter() {
+            if date(&kline.time) < date(&rehab.time) {
+                adjust_kline(kline, rehab.fwd_factor_a, rehab.fwd_factor_b);
+            }
+            if date(&kline.time) <= date(&rehab.time) {
+                adjust_last_close(kline, rehab.fwd_factor_a, rehab.fwd_factor_b);
+            }
+        }
+    }
+
+    kl_list
+}
+
+/// Converts unadjusted klines to backward adjusted (后复权) prices.
+///
+/// Every rehab event on or before a kline is applied to it, latest first. The last close is
+/// adjusted by the events on or before the day before, so not by one on the kline's own day.
+pub fn backward_adjust(mut kl_list: Vec<KLine>, rehab_list: &[Rehab]) -> Vec<KLine> {
+    let mut rehab_list: Vec<&Rehab> = rehab_list.iter().collect();
+    rehab_list.sort_by(|a, b| date(&b.time).cmp(date(&a.time)));
+
+    for kline in kl_list.iter_mut() {
+        for rehab in rehab_list.iter() {
+            if date(&kline.time) >= date(&rehab.time) {
+                adjust_kline(kline, rehab.bwd_factor_a, rehab.bwd_factor_b);
+            }
+            if date(&kline.time) > date(&rehab.time) {
+                adjust_last_close(kline, rehab.bwd_factor_a, rehab.bwd_factor_b);
+            }
+        }
+    }
+
+    kl_list
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{kline, rehab};
+
+    fn closes(kl_list: &[KLine]) -> Vec<(f64, f64)> {
+        kl_list
+            .iter()
+            .map(|kline| (kline.last_close_price.unwrap(), kline.close_price.unwrap()))
+            .collect()
+    }
+
+    // a 2 for 1 split going ex on 01-03, the unadjusted close halves from 20 to 10
+    fn split_klines() -> Vec<KLine> {
+        vec![
+            kline("2022-01-01 00:00:00", 20.0, 20.0),
+            kline("2022-01-02 00:00:00", 20.0, 20.0),
+            kline("2022-01-03 00:00:00", 20.0, 10.0),
+            kline("2022-01-04 00:00:00", 10.0, 10.0),
+        ]
+    }
+
+    #[test]
+    fn forward_adjusts_one_event() {
+        let rehab_list = [rehab("2022-01-03", (0.5, 0.0), (2.0, 0.0))];
+        let kl_list = forward_adjust(split_klines(), &rehab_list);
+        assert_eq!(
+            closes(&kl_list),
+            vec![(10.0, 10.0), (10.0, 10.0), (10.0, 10.0), (10.0, 10.0)]
+        );
+    }
+
+    #[test]
+    fn backward_adjusts_one_event() {
+        let rehab_list = [rehab("2022-01-03", (0.5, 0.0), (2.0, 0.0))];
+        let kl_list = backward_adjust(split_klines(), &rehab_list);
+        assert_eq!(
+            closes(&kl_list),
+            vec![(20.0, 20.0), (20.0, 20.0), (20.0, 20.0), (20.0, 20.0)]
+        );
+    }
+
+    // a 2 for 1 split going ex on 01-02, then a dividend of 1 going ex on 01-03
+    fn composed_klines() -> Vec<KLine> {
+        vec![
+            kline("2022-01-01", 40.0, 40.0),
+            kline("2022-01-02", 40.0, 20.0),
+            kline("2022-01-03", 20.0, 19.0),
+            kline("2022-01-04", 19.0, 19.0),
+        ]
+    }
+
+    fn composed_rehab_list() -> [Rehab; 2] {
+        // listed latest first, the order must not matter
+        [
+            rehab("2022-01-03", (1.0, -1.0), (1.0, 1.0)),
+            rehab("2022-01-02", (0.5, 0.0), (2.0, 0.0)),
+        ]
+    }
+
+    #[test]
+    fn forward_composes_events() {
+        let kl_list = forward_adjust(composed_klines(), &composed_rehab_list());
+        assert_eq!(
+            closes(&kl_list),
+            vec![(19.0, 19.0), (19.0, 19.0), (19.0, 19.0), (19.0, 19.0)]
+        );
+    }
+
+    #[test]
+    fn backward_composes_events() {
+        let kl_list = backward_adjust(composed_klines(), &composed_rehab_list());
+        assert_eq!(
+            closes(&kl_list),
+            vec![(40.0, 40.0), (40.0, 40.0), (40.0, 40.0), (40.0, 40.0)]
+        );
+    }
+}
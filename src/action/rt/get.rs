@@ -0,0 +1,60 @@
+use super::super::common::{Security, TimeShare};
+use crate::{
+    FutuRequest,
+    Qot_GetRT::{Request, Response, C2S},
+};
+use protobuf::MessageField;
+
+pub const PROTO_ID: u32 = 3008;
+
+#[derive(Debug)]
+pub struct GetRTRequest(Security);
+
+impl Into<Request> for GetRTRequest {
+    fn into(self) -> Request {
+        let mut req = Request::new();
+        let mut c2s = C2S::new();
+        c2s.security = MessageField::some(self.0.into());
+        req.c2s = MessageField::some(c2s);
+
+        req
+    }
+}
+
+impl GetRTRequest {
+    pub fn new(security: Security) -> Self {
+        GetRTRequest(security)
+    }
+
+    pub fn security(&self) -> &Security {
+        &self.0
+    }
+}
+
+#[derive(Debug)]
+pub struct GetRTResponse {
+    pub security: Security,
+    pub rt_list: Vec<TimeShare>,
+}
+
+impl From<Response> for GetRTResponse {
+    fn from(resp: Response) -> Self {
+        let mut rt_list = Vec::new();
+        for rt in resp.s2c.rtList.iter().cloned() {
+            rt_list.push(rt.into());
+        }
+
+        GetRTResponse {
+            security: resp.s2c.security.to_owned().unwrap().into(),
+            rt_list,
+        }
+    }
+}
+
+impl FutuRequest for GetRTRequest {
+    const PROTO_ID: u32 = PROTO_ID;
+
+    type Request = Request;
+    type Response = Response;
+    type Output = GetRTResponse;
+}
@@ -1,5 +1,5 @@
 use super::super::common::{Security, TimeShare};
-use crate::{Common::RetType, Qot_UpdateRT::Response};
+use crate::Qot_UpdateRT::Response;
 
 pub const PROTO_ID: u32 = 3009;
 
@@ -22,11 +22,3 @@ impl From<Response> for UpdateRTResponse {
         }
     }
 }
-
-pub fn check_response(resp: Response) -> crate::Result<UpdateRTResponse> {
-    if resp.retType() == RetType::RetType_Succeed as i32 {
-        return Ok(resp.into());
-    }
-
-    Err(resp.retMsg().into())
-}
@@ -1,7 +1,6 @@
 use super::common::{PreAfterMarketData, Security, SecurityVec};
 use crate::{
-    Common::RetType,
-    Frame,
+    FutuRequest,
     Qot_GetSecuritySnapshot::{self, Request, Response, C2S},
 };
 use protobuf::MessageField;
@@ -26,10 +25,6 @@ impl GetSecuritySnapshotRequest {
     pub fn new(security_list: Vec<Security>) -> Self {
         GetSecuritySnapshotRequest(security_list)
     }
-
-    pub fn into_frame(self) -> Frame<Request> {
-        Frame::new(self.into(), PROTO_ID)
-    }
 }
 
 #[derive(Debug)]
@@ -154,10 +149,10 @@ impl From<Response> for GetSecuritySnapshotResponse {
     }
 }
 
-pub fn check_response(resp: Response) -> crate::Result<GetSecuritySnapshotResponse> {
-    if resp.retType() == RetType::RetType_Succeed as i32 {
-        return Ok(resp.into());
-    }
+impl FutuRequest for GetSecuritySnapshotRequest {
+    const PROTO_ID: u32 = PROTO_ID;
 
-    Err(resp.retMsg().into())
+    type Request = Request;
+    type Response = Response;
+    type Output = GetSecuritySnapshotResponse;
 }
@@ -0,0 +1,83 @@
+use super::common::{Security, SecurityStaticInfo, SecurityVec};
+use crate::{
+    FutuRequest,
+    Qot_Common::{QotMarket, SecurityType},
+    Qot_GetStaticInfo::{Request, Response, C2S},
+};
+use protobuf::MessageField;
+
+const PROTO_ID: u32 = 3202;
+
+/// When `security_list` is not empty, `market` and `sec_type` are ignored by OpenD.
+#[derive(Debug)]
+pub struct GetStaticInfoRequest {
+    market: Option<QotMarket>,
+    sec_type: Option<SecurityType>,
+    security_list: Vec<Security>,
+}
+
+impl Into<Request> for GetStaticInfoRequest {
+    fn into(self) -> Request {
+        let mut req = Request::new();
+        let mut c2s = C2S::new();
+
+        if let Some(market) = self.market {
+            c2s.set_market(market as i32);
+        }
+
+        if let Some(sec_type) = self.sec_type {
+            c2s.set_secType(sec_type as i32);
+        }
+
+        c2s.securityList = SecurityVec(self.security_list).into();
+        req.c2s = MessageField::some(c2s);
+
+        req
+    }
+}
+
+impl GetStaticInfoRequest {
+    pub fn new(
+        market: Option<QotMarket>,
+        sec_type: Option<SecurityType>,
+        security_list: Vec<Security>,
+    ) -> Self {
+        GetStaticInfoRequest {
+            market,
+            sec_type,
+            security_list,
+        }
+    }
+
+    pub fn by_market(market: QotMarket, sec_type: SecurityType) -> Self {
+        GetStaticInfoRequest::new(Some(market), Some(sec_type), Vec::new())
+    }
+
+    pub fn by_security_list(security_list: Vec<Security>) -> Self {
+        GetStaticInfoRequest::new(None, None, security_list)
+    }
+}
+
+#[derive(Debug)]
+pub struct GetStaticInfoResponse {
+    pub static_info_list: Vec<SecurityStaticInfo>,
+}
+
+impl From<Response> for GetStaticInfoResponse {
+    fn from(resp: Response) -> Self {
+        let mut static_info_list = Vec::new();
+        for static_info in resp.s2c.staticInfoList.iter().cloned() {
+            static_info_list.push(static_info.into());
+        }
+
+        GetStaticInfoResponse { static_info_list }
+    }
+}
+
+impl FutuRequest for GetStaticInfoRequest {
+    const PROTO_ID: u32 = PROTO_ID;
+
+    type Request = Request;
+    type Response = Response;
+    type Output = GetStaticInfoResponse;
+}
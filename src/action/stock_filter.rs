@@ -1,7 +1,6 @@
 use super::common::Security;
 use crate::{
-    Common::RetType,
-    Frame,
+    FutuRequest,
     Qot_Common::QotMarket,
     Qot_StockFilter::{self, AccumulateField, Request, Response, SortDir, StockField, C2S},
 };
@@ -55,11 +54,7 @@ impl Into<Qot_StockFilter::BaseFilter> for BaseFilter {
         base_filter.filterMin = self.filter_min;
         base_filter.filterMax = self.filter_max;
         base_filter.isNoFilter = self.is_no_filter;
-        base_filter.sortDir = if self.sort_dir.is_some() {
-            Some(self.sort_dir.unwrap() as i32)
-        } else {
-            None
-        };
+        base_filter.sortDir = self.sort_dir.map(|sort_dir| sort_dir as i32);
 
         base_filter
     }
@@ -114,11 +109,7 @@ impl Into<Qot_StockFilter::AccumulateFilter> for AccumulateFilter {
         accumulate_filter.filterMin = self.filter_min;
         accumulate_filter.filterMax = self.filter_max;
         accumulate_filter.isNoFilter = self.is_no_filter;
-        accumulate_filter.sortDir = if self.sort_dir.is_some() {
-            Some(self.sort_dir.unwrap() as i32)
-        } else {
-            None
-        };
+        accumulate_filter.sortDir = self.sort_dir.map(|sort_dir| sort_dir as i32);
         accumulate_filter.set_days(self.days);
 
         accumulate_filter
@@ -142,8 +133,8 @@ impl Into<Request> for GetStockFilterRequest {
         c2s.set_num(self.num);
         c2s.set_market(self.market as i32);
 
-        if self.plate.is_some() {
-            c2s.plate = MessageField::some(self.plate.unwrap().into());
+        if let Some(plate) = self.plate {
+            c2s.plate = MessageField::some(plate.into());
         }
 
         c2s.baseFilterList = BaseFilterVec(self.base_filter_list).into();
@@ -173,10 +164,6 @@ impl GetStockFilterRequest {
             accumulate_filter_list,
         }
     }
-
-    pub fn into_frame(self) -> Frame<Request> {
-        Frame::new(self.into(), PROTO_ID)
-    }
 }
 
 #[derive(Debug)]
@@ -286,10 +273,10 @@ impl From<Response> for GetStockFilterResponse {
     }
 }
 
-pub fn check_response(resp: Response) -> crate::Result<GetStockFilterResponse> {
-    if resp.retType() == RetType::RetType_Succeed as i32 {
-        return Ok(resp.into());
-    }
+impl FutuRequest for GetStockFilterRequest {
+    const PROTO_ID: u32 = PROTO_ID;
 
-    Err(resp.retMsg().into())
+    type Request = Request;
+    type Response = Response;
+    type Output = GetStockFilterResponse;
 }
@@ -0,0 +1,60 @@
+use super::common::ConnSubInfo;
+use crate::{
+    FutuRequest,
+    Qot_GetSubInfo::{Request, Response, C2S},
+};
+use protobuf::MessageField;
+
+pub const PROTO_ID: u32 = 3003;
+
+#[derive(Debug)]
+pub struct GetSubInfoRequest {
+    is_req_all_conn: Option<bool>,
+}
+
+impl Into<Request> for GetSubInfoRequest {
+    fn into(self) -> Request {
+        let mut req = Request::new();
+        let mut c2s = C2S::new();
+        c2s.isReqAllConn = self.is_req_all_conn;
+        req.c2s = MessageField::some(c2s);
+
+        req
+    }
+}
+
+impl GetSubInfoRequest {
+    pub fn new(is_req_all_conn: Option<bool>) -> Self {
+        GetSubInfoRequest { is_req_all_conn }
+    }
+}
+
+#[derive(Debug)]
+pub struct GetSubInfoResponse {
+    pub conn_sub_info_list: Vec<ConnSubInfo>,
+    pub total_used_quota: i32,
+    pub remain_quota: i32,
+}
+
+impl From<Response> for GetSubInfoResponse {
+    fn from(resp: Response) -> Self {
+        let mut conn_sub_info_list = Vec::new();
+        for conn_sub_info in resp.s2c.connSubInfoList.iter().cloned() {
+            conn_sub_info_list.push(conn_sub_info.into());
+        }
+
+        GetSubInfoResponse {
+            conn_sub_info_list,
+            total_used_quota: resp.s2c.totalUsedQuota(),
+            remain_quota: resp.s2c.remainQuota(),
+        }
+    }
+}
+
+impl FutuRequest for GetSubInfoRequest {
+    const PROTO_ID: u32 = PROTO_ID;
+
+    type Request = Request;
+    type Response = Response;
+    type Output = GetSubInfoResponse;
+}
@@ -1,13 +1,13 @@
 use super::common::{Security, SecurityVec};
 use crate::{
-    Common::RetType,
-    Frame,
+    request::Ack,
+    FutuRequest,
     Qot_Common::{RehabType, SubType},
     Qot_Sub::{Request, Response, C2S},
 };
 use protobuf::MessageField;
 
-const PROTO_ID: u32 = 3001;
+pub const PROTO_ID: u32 = 3001;
 
 #[derive(Debug)]
 pub struct SubscribeRequest {
@@ -89,15 +89,27 @@ impl SubscribeRequest {
         }
     }
 
-    pub(crate) fn into_frame(self) -> Frame<Request> {
-        Frame::new(self.into(), PROTO_ID)
+    pub(crate) fn security_list(&self) -> &[Security] {
+        &self.security_list
+    }
+
+    pub(crate) fn sub_type_list(&self) -> &[SubType] {
+        &self.sub_type_list
     }
-}
 
-pub fn check_response(resp: Response) -> crate::Result<()> {
-    if resp.retType() == RetType::RetType_Succeed as i32 {
-        return Ok(());
+    pub(crate) fn is_sub_or_un_sub(&self) -> bool {
+        self.is_sub_or_un_sub
     }
 
-    Err(format!("{}: {}", resp.retType(), resp.retMsg()).into())
+    pub(crate) fn is_unsub_all(&self) -> bool {
+        self.is_unsub_all.unwrap_or(false)
+    }
+}
+
+impl FutuRequest for SubscribeRequest {
+    const PROTO_ID: u32 = PROTO_ID;
+
+    type Request = Request;
+    type Response = Response;
+    type Output = Ack;
 }
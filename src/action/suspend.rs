@@ -0,0 +1,200 @@
+use super::common::{Security, SecurityVec};
+use crate::{
+    FutuRequest,
+    Qot_GetSuspend::{self, Request, Response, C2S},
+};
+use chrono::{Datelike, NaiveDate, Weekday};
+use protobuf::MessageField;
+
+const PROTO_ID: u32 = 3201;
+
+#[derive(Debug)]
+pub struct GetSuspendRequest {
+    security_list: Vec<Security>,
+    begin_time: String,
+    end_time: String,
+}
+
+impl Into<Request> for GetSuspendRequest {
+    fn into(self) -> Request {
+        let mut req = Request::new();
+        let mut c2s = C2S::new();
+        c2s.securityList = SecurityVec(self.security_list).into();
+        c2s.set_beginTime(self.begin_time);
+        c2s.set_endTime(self.end_time);
+        req.c2s = MessageField::some(c2s);
+
+        req
+    }
+}
+
+impl GetSuspendRequest {
+    pub fn new(security_list: Vec<Security>, begin_time: String, end_time: String) -> Self {
+        GetSuspendRequest {
+            security_list,
+            begin_time,
+            end_time,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Suspend {
+    pub time: String,
+    pub timestamp: Option<f64>,
+}
+
+/// Consecutive suspended days, both ends included.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SuspendRange {
+    pub begin: NaiveDate,
+    pub end: NaiveDate,
+}
+
+impl SuspendRange {
+    pub fn contains(&self, date: NaiveDate) -> bool {
+        self.begin <= date && date <= self.end
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct SecuritySuspend {
+    pub security: Security,
+    /// The suspended days as OpenD lists them.
+    pub suspend_list: Vec<Suspend>,
+    /// `suspend_list` merged into ranges, a weekend between two suspended days doesn't break one.
+    pub suspend_ranges: Vec<SuspendRange>,
+}
+
+impl SecuritySuspend {
+    pub fn is_suspended_on(&self, date: NaiveDate) -> bool {
+        self.suspend_ranges.iter().any(|range| range.contains(date))
+    }
+}
+
+// the days are "yyyy-MM-dd", possibly followed by a clock
+fn parse_date(time: &str) -> Option<NaiveDate> {
+    NaiveDate::parse_from_str(time.get(..10)?, "%Y-%m-%d").ok()
+}
+
+fn is_weekend(date: NaiveDate) -> bool {
+    matches!(date.weekday(), Weekday::Sat | Weekday::Sun)
+}
+
+fn only_weekends_between(from: NaiveDate, to: NaiveDate) -> bool {
+    from.iter_days()
+        .skip(1)
+        .take_while(|date| *date < to)
+        .all(is_weekend)
+}
+
+fn merge_ranges(suspend_list: &[Suspend]) -> Vec<SuspendRange> {
+    let mut days: Vec<NaiveDate> = suspend_list
+        .iter()
+        .filter_map(|suspend| parse_date(&suspend.time))
+        .collect();
+    days.sort();
+    days.dedup();
+
+    let mut ranges: Vec<SuspendRange> = Vec::new();
+    for day in days {
+        match ranges.last_mut() {
+            Some(range) if only_weekends_between(range.end, day) => {
+                range.end = day;
+            }
+            _ => ranges.push(SuspendRange {
+                begin: day,
+                end: day,
+            }),
+        }
+    }
+
+    ranges
+}
+
+impl From<Qot_GetSuspend::SecuritySuspend> for SecuritySuspend {
+    fn from(security_suspend: Qot_GetSuspend::SecuritySuspend) -> Self {
+        let mut suspend_list = Vec::new();
+        for suspend in &security_suspend.suspendList {
+            suspend_list.push(Suspend {
+                time: suspend.time().into(),
+                timestamp: suspend.timestamp,
+            });
+        }
+
+        SecuritySuspend {
+            security: security_suspend.security.unwrap().into(),
+            suspend_ranges: merge_ranges(&suspend_list),
+            suspend_list,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct GetSuspendResponse(pub Vec<SecuritySuspend>);
+
+impl From<Response> for GetSuspendResponse {
+    fn from(resp: Response) -> Self {
+        let mut security_suspend_list = Vec::new();
+        for security_suspend in resp.s2c.SecuritySuspendList.iter().cloned() {
+            security_suspend_list.push(security_suspend.into());
+        }
+
+        GetSuspendResponse(security_suspend_list)
+    }
+}
+
+impl GetSuspendResponse {
+    pub fn into_inner(self) -> Vec<SecuritySuspend> {
+        self.0
+    }
+
+    /// Securities suspended on `date`.
+    pub fn suspended_on(&self, date: NaiveDate) -> Vec<&Security> {
+        self.0
+            .iter()
+            .filter(|security_suspend| security_suspend.is_suspended_on(date))
+            .map(|security_suspend| &security_suspend.security)
+            .collect()
+    }
+}
+
+impl FutuRequest for GetSuspendRequest {
+    const PROTO_ID: u32 = PROTO_ID;
+
+    type Request = Request;
+    type Response = Response;
+    type Output = GetSuspendResponse;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{date, suspend};
+
+    fn range(begin: &str, end: &str) -> SuspendRange {
+        SuspendRange {
+            begin: date(begin),
+            end: date(end),
+        }
+    }
+
+    #[test]
+    fn merges_consecutive_days_across_weekends() {
+        // 2022-01-07 is a Friday
+        let suspend_list = [
+            suspend("2022-01-06"),
+            suspend("2022-01-05"),
+            suspend("2022-01-07"),
+            suspend("2022-01-10 00:00:00"),
+            suspend("2022-01-12"),
+        ];
+        assert_eq!(
+            merge_ranges(&suspend_list),
+            vec![
+                range("2022-01-05", "2022-01-10"),
+                range("2022-01-12", "2022-01-12")
+            ]
+        );
+    }
+}
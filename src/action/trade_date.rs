@@ -0,0 +1,98 @@
+use super::common::Security;
+use crate::{
+    FutuRequest,
+    Qot_Common::{TradeDateMarket, TradeDateType},
+    Qot_RequestTradeDate::{self, Request, Response, C2S},
+};
+use protobuf::{Enum, MessageField};
+
+const PROTO_ID: u32 = 3219;
+
+/// When `security` is set, `market` is ignored by OpenD.
+#[derive(Debug)]
+pub struct RequestTradeDateRequest {
+    market: TradeDateMarket,
+    begin_time: String,
+    end_time: String,
+    security: Option<Security>,
+}
+
+impl Into<Request> for RequestTradeDateRequest {
+    fn into(self) -> Request {
+        let mut req = Request::new();
+        let mut c2s = C2S::new();
+        c2s.set_market(self.market as i32);
+        c2s.set_beginTime(self.begin_time);
+        c2s.set_endTime(self.end_time);
+
+        if let Some(security) = self.security {
+            c2s.security = MessageField::some(security.into());
+        }
+
+        req.c2s = MessageField::some(c2s);
+
+        req
+    }
+}
+
+impl RequestTradeDateRequest {
+    pub fn new(
+        market: TradeDateMarket,
+        begin_time: String,
+        end_time: String,
+        security: Option<Security>,
+    ) -> Self {
+        RequestTradeDateRequest {
+            market,
+            begin_time,
+            end_time,
+            security,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct TradeDate {
+    pub time: String,
+    pub timestamp: Option<f64>,
+    pub trade_date_type: TradeDateType,
+}
+
+impl From<Qot_RequestTradeDate::TradeDate> for TradeDate {
+    fn from(trade_date: Qot_RequestTradeDate::TradeDate) -> Self {
+        TradeDate {
+            time: trade_date.time().into(),
+            timestamp: trade_date.timestamp,
+            trade_date_type: TradeDateType::from_i32(trade_date.tradeDateType())
+                .unwrap_or(TradeDateType::TradeDateType_Whole),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct RequestTradeDateResponse(pub Vec<TradeDate>);
+
+impl From<Response> for RequestTradeDateResponse {
+    fn from(resp: Response) -> Self {
+        let mut trade_date_list = Vec::new();
+        for trade_date in resp.s2c.tradeDateList.iter().cloned() {
+            trade_date_list.push(trade_date.into());
+        }
+
+        RequestTradeDateResponse(trade_date_list)
+    }
+}
+
+impl RequestTradeDateResponse {
+    pub fn into_inner(self) -> Vec<TradeDate> {
+        self.0
+    }
+}
+
+impl FutuRequest for RequestTradeDateRequest {
+    const PROTO_ID: u32 = PROTO_ID;
+
+    type Request = Request;
+    type Response = Response;
+    type Output = RequestTradeDateResponse;
+}
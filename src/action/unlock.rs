@@ -1,10 +1,12 @@
 use crate::{
+    request::Ack,
     Common::RetType,
-    Frame,
+    FutuRequest, ResponseError,
     Trd_Common::SecurityFirm,
     Trd_UnlockTrade::{Request, Response, C2S},
 };
 use protobuf::MessageField;
+use std::fmt;
 
 const PROTO_ID: u32 = 2005;
 
@@ -40,15 +42,68 @@ impl UnlockRequest {
         }
     }
 
-    pub fn into_frame(self) -> Frame<Request> {
-        Frame::new(self.into(), PROTO_ID)
+    /// Locks trading again, no password is needed.
+    pub fn lock(security_firm: Option<SecurityFirm>) -> Self {
+        UnlockRequest {
+            unlock: false,
+            pwd_md5: None,
+            security_firm,
+        }
     }
 }
 
-pub fn check_response(resp: Response) -> crate::Result<()> {
-    if resp.retType() == RetType::RetType_Succeed as i32 {
-        return Ok(());
+/// Lowercase hex MD5 of the trade password, as OpenD expects it.
+pub fn pwd_md5(pwd: &str) -> String {
+    format!("{:x}", md5::compute(pwd))
+}
+
+/// Whether a trade call was refused only because trading is locked: OpenD answered with
+/// `RetType_Failed` and either one of `err_codes` or a `retMsg` asking to unlock first.
+/// Timeouts, disconnects and any other error may have reached the exchange and never count.
+pub(crate) fn is_need_unlock(err: &crate::Error, err_codes: &[i32]) -> bool {
+    match err.downcast_ref::<ResponseError>() {
+        Some(ResponseError {
+            ret_type,
+            ret_msg,
+            err_code,
+            ..
+        }) if *ret_type == RetType::RetType_Failed as i32 => {
+            err_code.is_some_and(|err_code| err_codes.contains(&err_code))
+                || ret_msg.to_lowercase().contains("unlock")
+                || ret_msg.contains("解锁")
+        }
+        _ => false,
     }
+}
+
+/// A trade call refused because trading got locked, where unlocking again failed as well.
+#[derive(Debug)]
+pub struct UnlockAgainError {
+    /// Why the trade call failed.
+    pub err: crate::Error,
+    pub unlock_err: crate::Error,
+}
+
+impl fmt::Display for UnlockAgainError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}, unlocking again failed: {}",
+            self.err, self.unlock_err
+        )
+    }
+}
+
+impl std::error::Error for UnlockAgainError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(self.err.as_ref())
+    }
+}
+
+impl FutuRequest for UnlockRequest {
+    const PROTO_ID: u32 = PROTO_ID;
 
-    Err(format!("{}: {}", resp.retType(), resp.retMsg()).into())
+    type Request = Request;
+    type Response = Response;
+    type Output = Ack;
 }
@@ -0,0 +1,217 @@
+use super::common::Security;
+use crate::{
+    FutuRequest,
+    GetUserInfo::{Request, Response, UpdateType, UserInfoField, C2S},
+    Qot_Common::{QotMarket, QotRight, SecurityType},
+};
+use protobuf::{Enum, MessageField};
+
+pub const PROTO_ID: u32 = 1005;
+
+#[derive(Debug)]
+pub struct GetUserInfoRequest {
+    field_list: Vec<UserInfoField>,
+}
+
+impl Into<Request> for GetUserInfoRequest {
+    fn into(self) -> Request {
+        let mut req = Request::new();
+        let mut c2s = C2S::new();
+        if !self.field_list.is_empty() {
+            let flag = self
+                .field_list
+                .into_iter()
+                .fold(0, |flag, field| flag | field as i32);
+            c2s.set_flag(flag);
+        }
+        req.c2s = MessageField::some(c2s);
+
+        req
+    }
+}
+
+impl GetUserInfoRequest {
+    /// An empty `field_list` asks for all the user info.
+    pub fn new(field_list: Vec<UserInfoField>) -> Self {
+        GetUserInfoRequest { field_list }
+    }
+}
+
+fn qot_right(qot_right: Option<i32>) -> Option<QotRight> {
+    qot_right.and_then(QotRight::from_i32)
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct GetUserInfoResponse {
+    pub nick_name: Option<String>,
+    pub avatar_url: Option<String>,
+    pub user_id: Option<i64>,
+    pub hk_qot_right: Option<QotRight>,        // 港股行情权限
+    pub us_qot_right: Option<QotRight>,        // 美股行情权限
+    pub cn_qot_right: Option<QotRight>,        // A股行情权限
+    pub hk_option_qot_right: Option<QotRight>, // 港股期权行情权限
+    pub has_us_option_qot_right: Option<bool>, // 是否有美股期权行情权限
+    pub hk_future_qot_right: Option<QotRight>, // 港股期货行情权限
+    pub us_future_qot_right: Option<QotRight>, // 美股期货行情权限
+    pub us_option_qot_right: Option<QotRight>, // 美股期权行情权限
+    pub us_index_qot_right: Option<QotRight>,  // 美股指数行情权限
+    pub us_otc_qot_right: Option<QotRight>,    // 美股OTC市场行情权限
+    pub sub_quota: Option<i32>,                // 订阅额度
+    pub history_kl_quota: Option<i32>,         // 历史K线额度
+    pub is_need_agree_disclaimer: Option<bool>,
+    pub update_type: Option<UpdateType>,
+    pub user_attribution: Option<i32>, // 用户注册归属地
+}
+
+impl From<Response> for GetUserInfoResponse {
+    fn from(resp: Response) -> Self {
+        let s2c = resp.s2c.unwrap_or_default();
+        GetUserInfoResponse {
+            nick_name: s2c.nickName,
+            avatar_url: s2c.avatarUrl,
+            user_id: s2c.userID,
+            hk_qot_right: qot_right(s2c.hkQotRight),
+            us_qot_right: qot_right(s2c.usQotRight),
+            cn_qot_right: qot_right(s2c.cnQotRight),
+            hk_option_qot_right: qot_right(s2c.hkOptionQotRight),
+            has_us_option_qot_right: s2c.hasUSOptionQotRight,
+            hk_future_qot_right: qot_right(s2c.hkFutureQotRight),
+            us_future_qot_right: qot_right(s2c.usFutureQotRight),
+            us_option_qot_right: qot_right(s2c.usOptionQotRight),
+            us_index_qot_right: qot_right(s2c.usIndexQotRight),
+            us_otc_qot_right: qot_right(s2c.usOtcQotRight),
+            sub_quota: s2c.subQuota,
+            history_kl_quota: s2c.historyKLQuota,
+            is_need_agree_disclaimer: s2c.isNeedAgreeDisclaimer,
+            update_type: s2c.updateType.and_then(UpdateType::from_i32),
+            user_attribution: s2c.userAttribution,
+        }
+    }
+}
+
+/// The security type a plain code implies, e.g. a numeric HK code is a stock or a warrant
+/// and `.DJI` a US index. Derivative and future codes answer None, their type can't be told
+/// from the code alone.
+pub fn plain_security_type(security: &Security) -> Option<SecurityType> {
+    let code = security.code.as_str();
+    match security.market {
+        QotMarket::QotMarket_HK_Security if code.chars().all(|c| c.is_ascii_digit()) => {
+            Some(SecurityType::SecurityType_Eqty)
+        }
+        QotMarket::QotMarket_US_Security if code.starts_with('.') => {
+            Some(SecurityType::SecurityType_Index)
+        }
+        QotMarket::QotMarket_US_Security
+            if code
+                .chars()
+                .all(|c| c.is_ascii_alphabetic() || c == '.' || c == '-') =>
+        {
+            Some(SecurityType::SecurityType_Eqty)
+        }
+        QotMarket::QotMarket_CNSH_Security | QotMarket::QotMarket_CNSZ_Security => {
+            Some(SecurityType::SecurityType_Eqty)
+        }
+        _ => None,
+    }
+}
+
+impl GetUserInfoResponse {
+    /// Names the quote rights that may cover the security along with their values, the one of
+    /// its security type when given, every right of its market otherwise.
+    ///
+    /// Markets without a right reported by OpenD answer an empty list.
+    pub fn qot_rights(
+        &self,
+        security: &Security,
+        security_type: Option<SecurityType>,
+    ) -> Vec<(&'static str, Option<QotRight>)> {
+        let hk = ("hk_qot_right", self.hk_qot_right);
+        let hk_option = ("hk_option_qot_right", self.hk_option_qot_right);
+        let hk_future = ("hk_future_qot_right", self.hk_future_qot_right);
+        let us = ("us_qot_right", self.us_qot_right);
+        let us_option = ("us_option_qot_right", self.us_option_qot_right);
+        let us_future = ("us_future_qot_right", self.us_future_qot_right);
+        let us_index = ("us_index_qot_right", self.us_index_qot_right);
+        let us_otc = ("us_otc_qot_right", self.us_otc_qot_right);
+
+        match security.market {
+            QotMarket::QotMarket_HK_Security | QotMarket::QotMarket_HK_Future => {
+                match security_type {
+                    Some(SecurityType::SecurityType_Drvt) => vec![hk_option],
+                    Some(SecurityType::SecurityType_Future) => vec![hk_future],
+                    Some(_) => vec![hk],
+                    None => vec![hk, hk_option, hk_future],
+                }
+            }
+            QotMarket::QotMarket_US_Security => match security_type {
+                Some(SecurityType::SecurityType_Drvt) => vec![us_option],
+                Some(SecurityType::SecurityType_Future) => vec![us_future],
+                Some(SecurityType::SecurityType_Index) => vec![us_index],
+                // OTC securities share the types of listed ones
+                Some(_) => vec![us, us_otc],
+                None => vec![us, us_option, us_future, us_index, us_otc],
+            },
+            QotMarket::QotMarket_CNSH_Security | QotMarket::QotMarket_CNSZ_Security => {
+                vec![("cn_qot_right", self.cn_qot_right)]
+            }
+            _ => vec![],
+        }
+    }
+
+    /// Fails with the missing rights when none of the rights that may cover the security
+    /// allows subscribing, a right not reported by OpenD allows it.
+    pub fn check_subscribable(
+        &self,
+        security: &Security,
+        security_type: Option<SecurityType>,
+    ) -> crate::Result<()> {
+        let qot_rights = self.qot_rights(security, security_type);
+        let is_refused = |right: &Option<QotRight>| {
+            matches!(right, Some(QotRight::QotRight_No | QotRight::QotRight_Bmp))
+        };
+        if qot_rights.is_empty() || !qot_rights.iter().all(|(_, right)| is_refused(right)) {
+            return Ok(());
+        }
+
+        let missing = qot_rights
+            .iter()
+            .map(|(name, right)| format!("{} is {:?}", name, right.unwrap()))
+            .collect::<Vec<_>>()
+            .join(", ");
+        Err(format!("{} can't be subscribed, {}", security, missing).into())
+    }
+}
+
+impl FutuRequest for GetUserInfoRequest {
+    const PROTO_ID: u32 = PROTO_ID;
+
+    type Request = Request;
+    type Response = Response;
+    type Output = GetUserInfoResponse;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::security;
+
+    #[test]
+    fn checks_the_right_of_the_plain_security_type() {
+        let user_info = GetUserInfoResponse {
+            hk_qot_right: Some(QotRight::QotRight_No),
+            hk_option_qot_right: Some(QotRight::QotRight_Level1),
+            hk_future_qot_right: Some(QotRight::QotRight_Level1),
+            ..Default::default()
+        };
+
+        let stock = security(QotMarket::QotMarket_HK_Security, "00700");
+        let stock_type = plain_security_type(&stock);
+        assert_eq!(stock_type, Some(SecurityType::SecurityType_Eqty));
+        assert!(user_info.check_subscribable(&stock, stock_type).is_err());
+
+        let future = security(QotMarket::QotMarket_HK_Security, "HSImain");
+        let future_type = plain_security_type(&future);
+        assert_eq!(future_type, None);
+        assert!(user_info.check_subscribable(&future, future_type).is_ok());
+    }
+}
@@ -1,7 +1,6 @@
 use super::super::common::SecurityStaticInfo;
 use crate::{
-    Common::RetType,
-    Frame,
+    FutuRequest,
     Qot_GetUserSecurity::{Request, Response, C2S},
 };
 use protobuf::MessageField;
@@ -26,10 +25,6 @@ impl GetUserSecurityRequest {
     pub fn new(group_name: String) -> Self {
         GetUserSecurityRequest(group_name)
     }
-
-    pub fn into_frame(self) -> Frame<Request> {
-        Frame::new(self.into(), PROTO_ID)
-    }
 }
 
 #[derive(Debug)]
@@ -52,10 +47,10 @@ impl From<Response> for GetUserSecurityResponse {
     }
 }
 
-pub fn check_response(resp: Response) -> crate::Result<GetUserSecurityResponse> {
-    if resp.retType() == RetType::RetType_Succeed as i32 {
-        return Ok(resp.into());
-    }
+impl FutuRequest for GetUserSecurityRequest {
+    const PROTO_ID: u32 = PROTO_ID;
 
-    Err(resp.retMsg().into())
+    type Request = Request;
+    type Response = Response;
+    type Output = GetUserSecurityResponse;
 }
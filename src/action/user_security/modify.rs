@@ -1,7 +1,7 @@
 use crate::{
     action::common::{Security, SecurityVec},
-    Common::RetType,
-    Frame,
+    request::Ack,
+    FutuRequest,
     Qot_ModifyUserSecurity::{ModifyUserSecurityOp, Request, Response, C2S},
 };
 use protobuf::MessageField;
@@ -35,16 +35,12 @@ impl ModifyUserSecurityRequest {
             security_list,
         }
     }
-
-    pub fn into_frame(self) -> Frame<Request> {
-        Frame::new(self.into(), PROTO_ID)
-    }
 }
 
-pub fn check_response(resp: Response) -> crate::Result<()> {
-    if resp.retType() == RetType::RetType_Succeed as i32 {
-        return Ok(());
-    }
+impl FutuRequest for ModifyUserSecurityRequest {
+    const PROTO_ID: u32 = PROTO_ID;
 
-    Err(format!("{}: {}", resp.retType(), resp.retMsg()).into())
+    type Request = Request;
+    type Response = Response;
+    type Output = Ack;
 }
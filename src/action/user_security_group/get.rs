@@ -1,6 +1,5 @@
 use crate::{
-    Common::RetType,
-    Frame,
+    FutuRequest,
     Qot_GetUserSecurityGroup::{GroupType, Request, Response, C2S},
 };
 use protobuf::{Enum, MessageField};
@@ -25,10 +24,6 @@ impl GetUserSecurityGroupRequest {
     pub fn new(group_type: GroupType) -> Self {
         GetUserSecurityGroupRequest(group_type)
     }
-
-    pub fn into_frame(self) -> Frame<Request> {
-        Frame::new(self.into(), PROTO_ID)
-    }
 }
 
 #[derive(Debug)]
@@ -56,10 +51,10 @@ impl From<Response> for GetUserSecurityGroupResponse {
     }
 }
 
-pub fn check_response(resp: Response) -> crate::Result<GetUserSecurityGroupResponse> {
-    if resp.retType() == RetType::RetType_Succeed as i32 {
-        return Ok(resp.into());
-    }
+impl FutuRequest for GetUserSecurityGroupRequest {
+    const PROTO_ID: u32 = PROTO_ID;
 
-    Err(resp.retMsg().into())
+    type Request = Request;
+    type Response = Response;
+    type Output = GetUserSecurityGroupResponse;
 }
@@ -0,0 +1,60 @@
+use crate::{
+    request::Ack,
+    FutuRequest,
+    Verification::{Request, Response, VerificationOp, VerificationType, C2S},
+};
+use protobuf::MessageField;
+
+const PROTO_ID: u32 = 1008;
+
+#[derive(Debug)]
+pub struct VerificationRequest {
+    type_: VerificationType,
+    op: VerificationOp,
+    code: Option<String>,
+}
+
+impl Into<Request> for VerificationRequest {
+    fn into(self) -> Request {
+        let mut req = Request::new();
+        let mut c2s = C2S::new();
+        c2s.set_type(self.type_ as i32);
+        c2s.set_op(self.op as i32);
+
+        if let Some(code) = self.code {
+            c2s.set_code(code);
+        }
+
+        req.c2s = MessageField::some(c2s);
+
+        req
+    }
+}
+
+impl VerificationRequest {
+    pub fn new(type_: VerificationType, op: VerificationOp, code: Option<String>) -> Self {
+        VerificationRequest { type_, op, code }
+    }
+
+    /// Asks OpenD to send a new verification code.
+    pub fn request(type_: VerificationType) -> Self {
+        VerificationRequest::new(type_, VerificationOp::VerificationOp_Request, None)
+    }
+
+    /// Submits the received verification code and continues the login.
+    pub fn input_and_login(type_: VerificationType, code: String) -> Self {
+        VerificationRequest::new(
+            type_,
+            VerificationOp::VerificationOp_InputAndLogin,
+            Some(code),
+        )
+    }
+}
+
+impl FutuRequest for VerificationRequest {
+    const PROTO_ID: u32 = PROTO_ID;
+
+    type Request = Request;
+    type Response = Response;
+    type Output = Ack;
+}
@@ -0,0 +1,395 @@
+use crate::{
+    action::trade_date::{RequestTradeDateRequest, TradeDate},
+    client::QotClient,
+    Qot_Common::{TradeDateMarket, TradeDateType},
+};
+use chrono::{Duration, NaiveDate};
+use std::collections::{BTreeMap, HashMap};
+
+const DATE_FORMAT: &str = "%Y-%m-%d";
+
+#[derive(Debug, Default)]
+struct MarketCalendar {
+    // loaded `[begin, end]` ranges keyed by begin, disjoint and never adjacent
+    ranges: BTreeMap<NaiveDate, NaiveDate>,
+    days: BTreeMap<NaiveDate, TradeDateType>,
+}
+
+impl MarketCalendar {
+    /// The loaded range `date` falls in.
+    fn range(&self, date: NaiveDate) -> Option<(NaiveDate, NaiveDate)> {
+        self.ranges
+            .range(..=date)
+            .next_back()
+            .filter(|(_, end)| date <= **end)
+            .map(|(begin, end)| (*begin, *end))
+    }
+
+    /// The parts of `[begin, end]` not loaded yet.
+    fn missing(&self, begin: NaiveDate, end: NaiveDate) -> Vec<(NaiveDate, NaiveDate)> {
+        let mut missing = Vec::new();
+        let mut from = begin;
+        for (range_begin, range_end) in self.ranges.iter() {
+            if *range_end < from {
+                continue;
+            }
+            if *range_begin > end {
+                break;
+            }
+            if *range_begin > from {
+                missing.push((from, *range_begin - Duration::days(1)));
+            }
+            from = *range_end + Duration::days(1);
+        }
+        if from <= end {
+            missing.push((from, end));
+        }
+
+        missing
+    }
+
+    fn add_range(&mut self, mut begin: NaiveDate, mut end: NaiveDate) {
+        let touching: Vec<(NaiveDate, NaiveDate)> = self
+            .ranges
+            .range(..=end + Duration::days(1))
+            .filter(|(_, range_end)| **range_end + Duration::days(1) >= begin)
+            .map(|(range_begin, range_end)| (*range_begin, *range_end))
+            .collect();
+        for (range_begin, range_end) in touching {
+            self.ranges.remove(&range_begin);
+            begin = begin.min(range_begin);
+            end = end.max(range_end);
+        }
+
+        self.ranges.insert(begin, end);
+    }
+}
+
+fn check_range(begin: NaiveDate, end: NaiveDate) -> crate::Result<()> {
+    if begin > end {
+        return Err(format!("begin {} is after end {}", begin, end).into());
+    }
+
+    Ok(())
+}
+
+/// Trading days per market, cached from `Qot_RequestTradeDate`.
+///
+/// Queries answer `None` when the date is outside of what has been loaded for the market.
+#[derive(Debug, Default)]
+pub struct TradingCalendar {
+    markets: HashMap<TradeDateMarket, MarketCalendar>,
+}
+
+impl TradingCalendar {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Makes sure `[begin, end]` is cached for `market`, only requesting the missing part.
+    pub async fn load(
+        &mut self,
+        client: &mut QotClient,
+        market: TradeDateMarket,
+        begin: NaiveDate,
+        end: NaiveDate,
+    ) -> crate::Result<()> {
+        check_range(begin, end)?;
+
+        let ranges = match self.markets.get(&market) {
+            Some(calendar) => calendar.missing(begin, end),
+            None => vec![(begin, end)],
+        };
+
+        for (begin, end) in ranges {
+            let trade_date_list = client
+                .request_trade_date(RequestTradeDateRequest::new(
+                    market,
+                    begin.format(DATE_FORMAT).to_string(),
+                    end.format(DATE_FORMAT).to_string(),
+                    None,
+                ))
+                .await?
+                .into_inner();
+            self.insert(market, begin, end, trade_date_list)?;
+        }
+
+        Ok(())
+    }
+
+    /// Caches `trade_date_list` as the complete list of trading days of `market` in `[begin, end]`,
+    /// next to the ranges cached before.
+    pub fn insert(
+        &mut self,
+        market: TradeDateMarket,
+        begin: NaiveDate,
+        end: NaiveDate,
+        trade_date_list: Vec<TradeDate>,
+    ) -> crate::Result<()> {
+        check_range(begin, end)?;
+
+        let mut days = BTreeMap::new();
+        for trade_date in trade_date_list {
+            let date = NaiveDate::parse_from_str(&trade_date.time, DATE_FORMAT)
+                .map_err(|e| format!("invalid trade date {}: {}", trade_date.time, e))?;
+            if date < begin || end < date {
+                return Err(format!("trade date {} is outside {} - {}", date, begin, end).into());
+            }
+            days.insert(date, trade_date.trade_date_type);
+        }
+
+        let calendar = self.markets.entry(market).or_default();
+        calendar.add_range(begin, end);
+        calendar.days.extend(days);
+
+        Ok(())
+    }
+
+    fn calendar(
+        &self,
+        market: TradeDateMarket,
+        date: NaiveDate,
+    ) -> Option<(&MarketCalendar, NaiveDate, NaiveDate)> {
+        let calendar = self.markets.get(&market)?;
+        let (begin, end) = calendar.range(date)?;
+        Some((calendar, begin, end))
+    }
+
+    /// `Some(None)` means the date is cached but is not a trading day.
+    pub fn trade_date_type(
+        &self,
+        market: TradeDateMarket,
+        date: NaiveDate,
+    ) -> Option<Option<TradeDateType>> {
+        self.calendar(market, date)
+            .map(|(calendar, _, _)| calendar.days.get(&date).copied())
+    }
+
+    pub fn is_trading_day(&self, market: TradeDateMarket, date: NaiveDate) -> Option<bool> {
+        self.trade_date_type(market, date)
+            .map(|trade_date_type| trade_date_type.is_some())
+    }
+
+    /// Whether `date` only trades in the morning or in the afternoon.
+    pub fn is_half_day(&self, market: TradeDateMarket, date: NaiveDate) -> Option<bool> {
+        self.trade_date_type(market, date).map(|trade_date_type| {
+            matches!(
+                trade_date_type,
+                Some(TradeDateType::TradeDateType_Morning)
+                    | Some(TradeDateType::TradeDateType_Afternoon)
+            )
+        })
+    }
+
+    /// First trading day strictly after `date`, None as well when it is past the loaded range.
+    pub fn next_trading_day(&self, market: TradeDateMarket, date: NaiveDate) -> Option<NaiveDate> {
+        let (calendar, _, end) = self.calendar(market, date)?;
+        if date >= end {
+            return None;
+        }
+
+        calendar
+            .days
+            .range(date + Duration::days(1)..=end)
+            .next()
+            .map(|(date, _)| *date)
+    }
+
+    /// Last trading day strictly before `date`, None as well when it is before the loaded range.
+    pub fn prev_trading_day(&self, market: TradeDateMarket, date: NaiveDate) -> Option<NaiveDate> {
+        let (calendar, begin, _) = self.calendar(market, date)?;
+        calendar
+            .days
+            .range(begin..date)
+            .next_back()
+            .map(|(date, _)| *date)
+    }
+
+    /// Trading days in `[begin, end]`, both ends must be cached.
+    pub fn trading_days_between(
+        &self,
+        market: TradeDateMarket,
+        begin: NaiveDate,
+        end: NaiveDate,
+    ) -> Option<Vec<NaiveDate>> {
+        let (calendar, _, range_end) = self.calendar(market, begin)?;
+        if end > range_end {
+            return None;
+        }
+
+        if begin > end {
+            return Some(Vec::new());
+        }
+
+        Some(
+            calendar
+                .days
+                .range(begin..=end)
+                .map(|(date, _)| *date)
+                .collect(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{date, trade_date};
+
+    const HK: TradeDateMarket = TradeDateMarket::TradeDateMarket_HK;
+
+    fn whole(time: &str) -> TradeDate {
+        trade_date(time, TradeDateType::TradeDateType_Whole)
+    }
+
+    // 2022-01-03 is a Monday
+    fn calendar() -> TradingCalendar {
+        let mut calendar = TradingCalendar::new();
+        calendar
+            .insert(
+                HK,
+                date("2022-01-01"),
+                date("2022-01-07"),
+                vec![
+                    whole("2022-01-03"),
+                    whole("2022-01-04"),
+                    whole("2022-01-05"),
+                    trade_date("2022-01-06", TradeDateType::TradeDateType_Morning),
+                    whole("2022-01-07"),
+                ],
+            )
+            .unwrap();
+        calendar
+    }
+
+    #[test]
+    fn merges_adjacent_and_overlapping_ranges() {
+        let mut calendar = calendar();
+        calendar
+            .insert(
+                HK,
+                date("2022-01-08"),
+                date("2022-01-11"),
+                vec![whole("2022-01-10"), whole("2022-01-11")],
+            )
+            .unwrap();
+        calendar
+            .insert(
+                HK,
+                date("2021-12-30"),
+                date("2022-01-04"),
+                vec![
+                    whole("2021-12-30"),
+                    whole("2021-12-31"),
+                    whole("2022-01-03"),
+                    whole("2022-01-04"),
+                ],
+            )
+            .unwrap();
+
+        assert_eq!(
+            calendar.trading_days_between(HK, date("2021-12-30"), date("2022-01-11")),
+            Some(
+                [
+                    "2021-12-30",
+                    "2021-12-31",
+                    "2022-01-03",
+                    "2022-01-04",
+                    "2022-01-05",
+                    "2022-01-06",
+                    "2022-01-07",
+                    "2022-01-10",
+                    "2022-01-11"
+                ]
+                .iter()
+                .map(|d| date(d))
+                .collect()
+            )
+        );
+    }
+
+    #[test]
+    fn leaves_the_gap_between_disjoint_ranges_unknown() {
+        let mut calendar = calendar();
+        calendar
+            .insert(
+                HK,
+                date("2022-02-01"),
+                date("2022-02-02"),
+                vec![whole("2022-02-01")],
+            )
+            .unwrap();
+
+        assert_eq!(calendar.is_trading_day(HK, date("2022-01-03")), Some(true));
+        assert_eq!(calendar.is_trading_day(HK, date("2022-01-20")), None);
+        assert_eq!(calendar.is_trading_day(HK, date("2022-02-01")), Some(true));
+        assert_eq!(calendar.is_trading_day(HK, date("2022-02-02")), Some(false));
+        assert_eq!(calendar.next_trading_day(HK, date("2022-01-07")), None);
+        assert_eq!(calendar.prev_trading_day(HK, date("2022-02-01")), None);
+        assert_eq!(
+            calendar.trading_days_between(HK, date("2022-01-03"), date("2022-02-01")),
+            None
+        );
+        assert_eq!(
+            calendar.markets[&HK].missing(date("2022-01-05"), date("2022-02-05")),
+            vec![
+                (date("2022-01-08"), date("2022-01-31")),
+                (date("2022-02-03"), date("2022-02-05"))
+            ]
+        );
+    }
+
+    #[test]
+    fn next_and_prev_trading_day() {
+        let calendar = calendar();
+        assert_eq!(
+            calendar.next_trading_day(HK, date("2022-01-01")),
+            Some(date("2022-01-03"))
+        );
+        assert_eq!(
+            calendar.next_trading_day(HK, date("2022-01-03")),
+            Some(date("2022-01-04"))
+        );
+        assert_eq!(calendar.next_trading_day(HK, date("2022-01-07")), None);
+        assert_eq!(
+            calendar.prev_trading_day(HK, date("2022-01-04")),
+            Some(date("2022-01-03"))
+        );
+        assert_eq!(calendar.prev_trading_day(HK, date("2022-01-03")), None);
+        assert_eq!(calendar.prev_trading_day(HK, date("2022-01-08")), None);
+    }
+
+    #[test]
+    fn is_half_day() {
+        let calendar = calendar();
+        assert_eq!(calendar.is_half_day(HK, date("2022-01-06")), Some(true));
+        assert_eq!(calendar.is_half_day(HK, date("2022-01-05")), Some(false));
+        assert_eq!(calendar.is_half_day(HK, date("2022-01-01")), Some(false));
+        assert_eq!(calendar.is_half_day(HK, date("2022-01-08")), None);
+    }
+
+    #[test]
+    fn trading_days_between() {
+        let calendar = calendar();
+        assert_eq!(
+            calendar.trading_days_between(HK, date("2022-01-01"), date("2022-01-04")),
+            Some(vec![date("2022-01-03"), date("2022-01-04")])
+        );
+        assert_eq!(
+            calendar.trading_days_between(HK, date("2022-01-05"), date("2022-01-02")),
+            Some(vec![])
+        );
+        assert_eq!(
+            calendar.trading_days_between(HK, date("2022-01-05"), date("2022-01-08")),
+            None
+        );
+    }
+
+    #[test]
+    fn rejects_begin_after_end() {
+        let mut calendar = TradingCalendar::new();
+        assert!(calendar
+            .insert(HK, date("2022-01-02"), date("2022-01-01"), vec![])
+            .is_err());
+    }
+}
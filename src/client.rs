@@ -5,56 +5,84 @@ use crate::{
             get::{GetBasicQotRequest, GetBasicQotResponse},
             update::UpdateBasicQotResponse,
         },
-        common::{PacketID, TrdHeader},
-        global_state::{self, GetGlobalStateRequest, GetGlobalStateResponse},
-        history_order_list::{self, GetHistoryOrderListRequest, GetHistoryOrderListResponse},
-        init_connect::{self, InitConnectRequest, InitConnectResponse},
-        ipo::{self, GetIpoListRequest, GetIpoListResponse},
-        keepalive::KeepAliveRequest,
-        kl::{self, update::UpdateKLResponse},
-        max_trd_qtys::{self, GetMaxTrdQtysRequest, GetMaxTrdQtysResponse},
-        order::{
+        capital_distribution::{GetCapitalDistributionRequest, GetCapitalDistributionResponse},
+        capital_flow::{GetCapitalFlowRequest, GetCapitalFlowResponse},
+        code_change::{GetCodeChangeRequest, GetCodeChangeResponse},
+        common::{PacketID, Security, TrdHeader},
+        delay_statistics::{GetDelayStatisticsRequest, GetDelayStatisticsResponse},
+        future_info::{GetFutureInfoRequest, GetFutureInfoResponse, MainContract},
+        global_state::{GetGlobalStateRequest, GetGlobalStateResponse},
+        history_kl_points::{GetHistoryKLPointsRequest, GetHistoryKLPointsResponse},
+        history_order_list::{GetHistoryOrderListRequest, GetHistoryOrderListResponse},
+        holding_change_list::{GetHoldingChangeListRequest, GetHoldingChangeListResponse},
+        init_connect::InitConnectResponse,
+        ipo::{GetIpoListRequest, GetIpoListResponse},
+        keepalive::{self, KeepAliveRequest},
+        kl::{
             self,
+            get::{GetKLRequest, GetKLResponse},
+            update::UpdateKLResponse,
+        },
+        market_state::{GetMarketStateRequest, GetMarketStateResponse},
+        max_trd_qtys::{GetMaxTrdQtysRequest, GetMaxTrdQtysResponse},
+        order::{
             modify::{ModifyOrderRequest, ModifyOrderResponse},
             place::{PlaceOrderRequest, PlaceOrderResponse},
         },
-        plate_security::{self, GetPlateSecurityRequest, GetPlateSecurityResponse},
-        position_list::{self, GetPositionListRequest, GetPositionListResponse},
+        owner_plate::{GetOwnerPlateRequest, GetOwnerPlateResponse},
+        plate_security::{GetPlateSecurityRequest, GetPlateSecurityResponse},
+        plate_set::{GetPlateSetRequest, GetPlateSetResponse},
+        position_list::{GetPositionListRequest, GetPositionListResponse},
         price_reminder::{
-            self,
             get::{GetPriceReminderRequest, GetPriceReminderResponse},
             set::{SetPriceReminderRequest, SetPriceReminderResponse},
         },
-        rt::{self, update::UpdateRTResponse},
-        security_snapshot::{self, GetSecuritySnapshotRequest, GetSecuritySnapshotResponse},
-        stock_filter::{self, GetStockFilterRequest, GetStockFilterResponse},
-        subscribe::{self, SubscribeRequest},
-        unlock::{self, UnlockRequest},
-        user_security::{
+        reference::{GetReferenceRequest, GetReferenceResponse},
+        reg_qot_push::RegQotPushRequest,
+        rehab::{RequestRehabRequest, RequestRehabResponse},
+        rt::{
             self,
+            get::{GetRTRequest, GetRTResponse},
+            update::UpdateRTResponse,
+        },
+        security_snapshot::{GetSecuritySnapshotRequest, GetSecuritySnapshotResponse},
+        static_info::{GetStaticInfoRequest, GetStaticInfoResponse},
+        stock_filter::{GetStockFilterRequest, GetStockFilterResponse},
+        sub_info::{GetSubInfoRequest, GetSubInfoResponse},
+        subscribe::SubscribeRequest,
+        suspend::{GetSuspendRequest, GetSuspendResponse},
+        trade_date::{RequestTradeDateRequest, RequestTradeDateResponse},
+        unlock::{self, UnlockAgainError, UnlockRequest},
+        user_info::{plain_security_type, GetUserInfoRequest, GetUserInfoResponse},
+        user_security::{
             get::{GetUserSecurityRequest, GetUserSecurityResponse},
             modify::ModifyUserSecurityRequest,
         },
-        user_security_group::{
-            self,
-            get::{GetUserSecurityGroupRequest, GetUserSecurityGroupResponse},
-        },
+        user_security_group::get::{GetUserSecurityGroupRequest, GetUserSecurityGroupResponse},
+        verification::VerificationRequest,
     },
-    serial_no, Connection, Frame,
+    frame::FrameRaw,
+    options::ConnectOptions,
+    request::check_ret,
+    Connection, Frame, FutuRequest,
+    Qot_Common::{KLType, RehabType, SubType},
     Trd_Common::{
         ModifyOrderOp, OrderType, SecurityFirm, TimeInForce, TrailType, TrdEnv, TrdMarket,
         TrdSecMarket, TrdSide,
     },
 };
+use chrono::NaiveDate;
+use protobuf::MessageFull;
 use std::{
+    collections::{HashSet, VecDeque},
     io::{Error, ErrorKind},
     sync::Arc,
 };
 use tokio::{
     net::{TcpStream, ToSocketAddrs},
-    sync::Mutex,
+    sync::{mpsc::Receiver, Mutex},
     task::JoinHandle,
-    time::{sleep, Duration},
+    time::{sleep, timeout, Duration},
 };
 
 pub struct QotClient {
@@ -64,34 +92,70 @@ pub struct QotClient {
 pub struct TrdClient {
     conn_id: u64,
     connection: Connection,
+    // security firm and password MD5 of the last unlock, None while locked
+    unlock_state: Option<(SecurityFirm, String)>,
+    // errCodes of a trade call refused because trading got locked, empty never unlocks again
+    need_unlock_err_codes: Vec<i32>,
 }
 
 pub struct SubClient {
     keep_alive_interval: i32,
     connection: Arc<Mutex<Connection>>,
     handle: Option<JoinHandle<()>>,
+    subscriptions: HashSet<(Security, SubType)>,
+    // pushes read while waiting for a response, handed out by `Subscriber::next_data` first
+    pending: VecDeque<FrameRaw>,
+    // quote rights checked before subscribing, fetched on the first subscribe
+    user_info: Option<GetUserInfoResponse>,
 }
 
-pub async fn qot_connect<T: ToSocketAddrs>(addr: T) -> crate::Result<QotClient> {
-    let socket = TcpStream::connect(addr).await?;
-    let connection = Connection::new(socket);
+async fn connect<T: ToSocketAddrs>(addr: T, options: &ConnectOptions) -> crate::Result<Connection> {
+    let socket = match options.get_connect_timeout() {
+        Some(connect_timeout) => match timeout(connect_timeout, TcpStream::connect(addr)).await {
+            Ok(socket) => socket?,
+            Err(_) => {
+                let err = Error::new(ErrorKind::TimedOut, "connect timeout");
+                return Err(err.into());
+            }
+        },
+        None => TcpStream::connect(addr).await?,
+    };
+
+    let mut connection = Connection::new(socket);
+    connection.set_read_timeout(options.get_read_timeout());
+    connection.set_push_timeout(options.get_push_timeout());
+    connection.set_proto_fmt(options.get_proto_fmt());
+    connection.set_max_body_len(options.get_max_body_len());
+    Ok(connection)
+}
+
+pub async fn qot_connect<T: ToSocketAddrs>(
+    addr: T,
+    options: ConnectOptions,
+) -> crate::Result<QotClient> {
+    let connection = connect(addr, &options).await?;
 
     let mut client = QotClient { connection };
-    client.init_connect().await?;
+    client.init_connect(&options).await?;
 
     Ok(client)
 }
 
-pub async fn sub_connect<T: ToSocketAddrs>(addr: T) -> crate::Result<SubClient> {
-    let socket = TcpStream::connect(addr).await?;
-    let connection = Connection::new(socket);
+pub async fn sub_connect<T: ToSocketAddrs>(
+    addr: T,
+    options: ConnectOptions,
+) -> crate::Result<SubClient> {
+    let connection = connect(addr, &options).await?;
 
     let mut client = SubClient {
         keep_alive_interval: 0,
         connection: Arc::new(Mutex::new(connection)),
         handle: None,
+        subscriptions: HashSet::new(),
+        pending: VecDeque::new(),
+        user_info: None,
     };
-    let init_connect_resp = client.init_connect().await?;
+    let init_connect_resp = client.init_connect(&options).await?;
     client.keep_alive_interval = init_connect_resp.keep_alive_interval;
 
     let conn = client.connection.clone();
@@ -109,105 +173,139 @@ pub async fn sub_connect<T: ToSocketAddrs>(addr: T) -> crate::Result<SubClient>
     Ok(client)
 }
 
-pub async fn trd_connect<T: ToSocketAddrs>(addr: T) -> crate::Result<TrdClient> {
-    let socket = TcpStream::connect(addr).await?;
-    let connection = Connection::new(socket);
+pub async fn trd_connect<T: ToSocketAddrs>(
+    addr: T,
+    options: ConnectOptions,
+) -> crate::Result<TrdClient> {
+    let connection = connect(addr, &options).await?;
 
     let mut client = TrdClient {
         conn_id: 0,
         connection,
+        unlock_state: None,
+        need_unlock_err_codes: Vec::new(),
     };
 
-    let init_connect_resp = client.init_connect().await?;
+    let init_connect_resp = client.init_connect(&options).await?;
     client.set_conn_id(init_connect_resp.conn_id);
 
     Ok(client)
 }
 
 impl TrdClient {
+    /// Sends any request and reads its response, see `FutuRequest`.
+    pub async fn request<R: FutuRequest>(&mut self, req: R) -> crate::Result<R::Output> {
+        self.connection.request(req).await
+    }
+
+    /// Hands out the frames read while waiting for responses that answer no request, e.g.
+    /// Notify pushes when `ConnectOptions::recv_notify` is set, see `Connection::raw_pushes`.
+    pub fn raw_pushes(&mut self, capacity: usize) -> Receiver<FrameRaw> {
+        self.connection.raw_pushes(capacity)
+    }
+
+    /// Frames dropped because the `raw_pushes` stream was full.
+    pub fn dropped_raw_pushes(&self) -> u64 {
+        self.connection.dropped_raw_pushes()
+    }
+
+    /// Sends a generated request message of a proto not wrapped yet, e.g.
+    /// `send_raw::<Qot_GetWarrant::Request, Qot_GetWarrant::Response>(3210, req)`.
+    pub async fn send_raw<Req: MessageFull, Resp: MessageFull>(
+        &mut self,
+        proto_id: u32,
+        req: Req,
+    ) -> crate::Result<Resp> {
+        self.connection.send_raw(proto_id, req).await
+    }
+
     fn set_conn_id(&mut self, id: u64) {
         self.conn_id = id;
     }
 
-    async fn init_connect(&mut self) -> crate::Result<InitConnectResponse> {
-        let frame = InitConnectRequest::default().into_frame();
-        self.connection.write_frame(&frame).await?;
-        let frame: Frame<crate::InitConnect::Response> = match self.connection.read_frame().await? {
-            Some(frame) => frame,
-            None => {
-                let err = Error::new(ErrorKind::ConnectionReset, "connection reset by server");
-                return Err(err.into());
+    async fn init_connect(
+        &mut self,
+        options: &ConnectOptions,
+    ) -> crate::Result<InitConnectResponse> {
+        self.request(options.init_connect_request()).await
+    }
+
+    pub async fn unlock(&mut self, security_firm: SecurityFirm, pwd: String) -> crate::Result<()> {
+        self.unlock_md5(security_firm, unlock::pwd_md5(&pwd)).await
+    }
+
+    /// Same as `unlock`, with the password already hashed by `unlock::pwd_md5`.
+    pub async fn unlock_md5(
+        &mut self,
+        security_firm: SecurityFirm,
+        pwd_md5: String,
+    ) -> crate::Result<()> {
+        let unlock_req = UnlockRequest::new(pwd_md5.clone(), Some(security_firm));
+        self.send_unlock(unlock_req).await?;
+        self.unlock_state = Some((security_firm, pwd_md5));
+        Ok(())
+    }
+
+    pub async fn lock(&mut self) -> crate::Result<()> {
+        let security_firm = self
+            .unlock_state
+            .as_ref()
+            .map(|(security_firm, _)| *security_firm);
+        self.send_unlock(UnlockRequest::lock(security_firm)).await?;
+        self.unlock_state = None;
+        Ok(())
+    }
+
+    pub fn is_unlocked(&self) -> bool {
+        self.unlock_state.is_some()
+    }
+
+    async fn send_unlock(&mut self, unlock_req: UnlockRequest) -> crate::Result<()> {
+        self.request(unlock_req).await?;
+        Ok(())
+    }
+
+    /// More `errCode`s meaning trading got locked, besides a `retMsg` asking to unlock. On
+    /// either, `place_order` and `modify_order` unlock again with the remembered password and
+    /// retry once.
+    pub fn set_need_unlock_err_codes(&mut self, err_codes: Vec<i32>) {
+        self.need_unlock_err_codes = err_codes;
+    }
+
+    /// Unlocks again with the remembered password when `err` says trading got locked,
+    /// otherwise gives `err` back.
+    async fn unlock_again(&mut self, err: crate::Error) -> crate::Result<()> {
+        match self.unlock_state.clone() {
+            Some((security_firm, pwd_md5))
+                if unlock::is_need_unlock(&err, &self.need_unlock_err_codes) =>
+            {
+                self.unlock_md5(security_firm, pwd_md5)
+                    .await
+                    .map_err(|unlock_err| UnlockAgainError { err, unlock_err }.into())
             }
-        };
-        init_connect::check_response(frame.body)
-    }
-
-    pub async fn unlock(&mut self, pwd: String) -> crate::Result<()> {
-        let pwd_md5 = format!("{:x}", md5::compute(pwd));
-        let unlock_req =
-            UnlockRequest::new(pwd_md5, Some(SecurityFirm::SecurityFirm_FutuSecurities));
-        let frame = unlock_req.into_frame();
-        self.connection.write_frame(&frame).await?;
-        let frame: Frame<crate::Trd_UnlockTrade::Response> =
-            match self.connection.read_frame().await? {
-                Some(frame) => frame,
-                None => {
-                    let err = Error::new(ErrorKind::ConnectionReset, "connection reset by server");
-                    return Err(err.into());
-                }
-            };
-        unlock::check_response(frame.body)
+            _ => Err(err),
+        }
     }
 
     pub async fn get_max_trd_qtys(
         &mut self,
         get_max_trd_qtys_req: GetMaxTrdQtysRequest,
     ) -> crate::Result<GetMaxTrdQtysResponse> {
-        let frame = get_max_trd_qtys_req.into_frame();
-        self.connection.write_frame(&frame).await?;
-        let frame: Frame<crate::Trd_GetMaxTrdQtys::Response> =
-            match self.connection.read_frame().await? {
-                Some(frame) => frame,
-                None => {
-                    let err = Error::new(ErrorKind::ConnectionReset, "connection reset by server");
-                    return Err(err.into());
-                }
-            };
-        max_trd_qtys::check_response(frame.body)
+        self.request(get_max_trd_qtys_req).await
     }
 
     pub async fn get_position_list(
         &mut self,
         get_position_list_req: GetPositionListRequest,
     ) -> crate::Result<GetPositionListResponse> {
-        let frame = get_position_list_req.into_frame();
-        self.connection.write_frame(&frame).await?;
-        let frame: Frame<crate::Trd_GetPositionList::Response> =
-            match self.connection.read_frame().await? {
-                Some(frame) => frame,
-                None => {
-                    let err = Error::new(ErrorKind::ConnectionReset, "connection reset by server");
-                    return Err(err.into());
-                }
-            };
-        position_list::check_response(frame.body)
+        self.request(get_position_list_req).await
     }
 
     pub async fn get_history_order_list(
         &mut self,
         get_history_order_list_req: GetHistoryOrderListRequest,
     ) -> crate::Result<GetHistoryOrderListResponse> {
-        let frame = get_history_order_list_req.into_frame();
-        self.connection.write_frame(&frame).await?;
-        let frame: Frame<crate::Trd_GetHistoryOrderList::Response> =
-            match self.connection.read_frame().await? {
-                Some(frame) => frame,
-                None => {
-                    let err = Error::new(ErrorKind::ConnectionReset, "connection reset by server");
-                    return Err(err.into());
-                }
-            };
-        history_order_list::check_response(frame.body)
+        self.request(get_history_order_list_req).await
     }
 
     pub async fn modify_order(
@@ -220,10 +318,10 @@ impl TrdClient {
         qty: Option<f64>,
         price: Option<f64>,
     ) -> crate::Result<ModifyOrderResponse> {
-        let modify_order_req = ModifyOrderRequest::new(
+        let mut modify_order_req = ModifyOrderRequest::new(
             PacketID {
                 conn_id: self.conn_id,
-                serial_no: serial_no(),
+                serial_no: self.connection.next_serial_no(),
             },
             TrdHeader {
                 acc_id,
@@ -235,17 +333,26 @@ impl TrdClient {
             qty,
             price,
         );
-        let frame = modify_order_req.into_frame();
-        self.connection.write_frame(&frame).await?;
-        let frame: Frame<crate::Trd_ModifyOrder::Response> =
-            match self.connection.read_frame().await? {
-                Some(frame) => frame,
-                None => {
-                    let err = Error::new(ErrorKind::ConnectionReset, "connection reset by server");
-                    return Err(err.into());
-                }
-            };
-        order::modify::check_response(frame.body)
+
+        match self.send_modify_order(modify_order_req.clone()).await {
+            Err(err) => {
+                self.unlock_again(err).await?;
+                modify_order_req.packet_id.serial_no = self.connection.next_serial_no();
+                self.send_modify_order(modify_order_req).await
+            }
+            modify_order_resp => modify_order_resp,
+        }
+    }
+
+    /// The frame header shares the serial number of the `PacketID`.
+    async fn send_modify_order(
+        &mut self,
+        modify_order_req: ModifyOrderRequest,
+    ) -> crate::Result<ModifyOrderResponse> {
+        let serial_no = modify_order_req.packet_id.serial_no;
+        self.connection
+            .request_with_serial_no(modify_order_req, serial_no)
+            .await
     }
 
     pub async fn place_order(
@@ -269,62 +376,90 @@ impl TrdClient {
         trail_value: Option<f64>,
         trail_spread: Option<f64>,
     ) -> crate::Result<PlaceOrderResponse> {
-        let mut place_order_req = PlaceOrderRequest::default();
-
-        place_order_req.packet_id = PacketID {
-            conn_id: self.conn_id,
-            serial_no: serial_no(),
+        let mut place_order_req = PlaceOrderRequest {
+            packet_id: PacketID {
+                conn_id: self.conn_id,
+                serial_no: self.connection.next_serial_no(),
+            },
+            header: TrdHeader {
+                trd_env,
+                acc_id,
+                trd_market,
+            },
+            trd_side,
+            order_type,
+            code,
+            qty,
+            price,
+            adjust_price,
+            adjust_side_and_limit,
+            sec_market,
+            remark,
+            time_in_force,
+            fill_outside_rth,
+            aux_price,
+            trail_type,
+            trail_value,
+            trail_spread,
         };
 
-        place_order_req.header = TrdHeader {
-            trd_env,
-            acc_id,
-            trd_market,
-        };
+        match self.send_place_order(place_order_req.clone()).await {
+            Err(err) => {
+                self.unlock_again(err).await?;
+                place_order_req.packet_id.serial_no = self.connection.next_serial_no();
+                self.send_place_order(place_order_req).await
+            }
+            place_order_resp => place_order_resp,
+        }
+    }
 
-        place_order_req.trd_side = trd_side;
-        place_order_req.order_type = order_type;
-        place_order_req.code = code;
-        place_order_req.qty = qty;
-        place_order_req.price = price;
-        place_order_req.adjust_price = adjust_price;
-        place_order_req.adjust_side_and_limit = adjust_side_and_limit;
-        place_order_req.sec_market = sec_market;
-        place_order_req.remark = remark;
-        place_order_req.time_in_force = time_in_force;
-        place_order_req.fill_outside_rth = fill_outside_rth;
-        place_order_req.aux_price = aux_price;
-        place_order_req.trail_type = trail_type;
-        place_order_req.trail_value = trail_value;
-        place_order_req.trail_spread = trail_spread;
-
-        let frame = place_order_req.into_frame();
-        self.connection.write_frame(&frame).await?;
-        let frame: Frame<crate::Trd_PlaceOrder::Response> =
-            match self.connection.read_frame().await? {
-                Some(frame) => frame,
-                None => {
-                    let err = Error::new(ErrorKind::ConnectionReset, "connection reset by server");
-                    return Err(err.into());
-                }
-            };
-        order::place::check_response(frame.body)
+    /// The frame header shares the serial number of the `PacketID`.
+    async fn send_place_order(
+        &mut self,
+        place_order_req: PlaceOrderRequest,
+    ) -> crate::Result<PlaceOrderResponse> {
+        let serial_no = place_order_req.packet_id.serial_no;
+        self.connection
+            .request_with_serial_no(place_order_req, serial_no)
+            .await
     }
 }
 
 impl SubClient {
-    async fn init_connect(&mut self) -> crate::Result<InitConnectResponse> {
-        let frame = InitConnectRequest::default().into_frame();
-        let mut connection = self.connection.lock().await;
-        connection.write_frame(&frame).await?;
-        let frame: Frame<crate::InitConnect::Response> = match connection.read_frame().await? {
-            Some(frame) => frame,
-            None => {
-                let err = Error::new(ErrorKind::ConnectionReset, "connection reset by server");
-                return Err(err.into());
-            }
-        };
-        init_connect::check_response(frame.body)
+    /// Sends any request and reads its response, pushes arriving meanwhile are kept for
+    /// `Subscriber::next_data`.
+    pub async fn request<R: FutuRequest>(&mut self, req: R) -> crate::Result<R::Output> {
+        let serial_no = self
+            .connection
+            .lock()
+            .await
+            .write_frame(&req.into_frame())
+            .await?;
+        let frame: Frame<R::Response> = self.read_response(R::PROTO_ID, serial_no).await?;
+        R::check_response(frame.body)
+    }
+
+    /// Sends a generated request message of a proto not wrapped yet, see `QotClient::send_raw`.
+    pub async fn send_raw<Req: MessageFull, Resp: MessageFull>(
+        &mut self,
+        proto_id: u32,
+        req: Req,
+    ) -> crate::Result<Resp> {
+        let serial_no = self
+            .connection
+            .lock()
+            .await
+            .write_frame(&Frame::new(req, proto_id))
+            .await?;
+        let frame: Frame<Resp> = self.read_response(proto_id, serial_no).await?;
+        Ok(frame.body)
+    }
+
+    async fn init_connect(
+        &mut self,
+        options: &ConnectOptions,
+    ) -> crate::Result<InitConnectResponse> {
+        self.request(options.init_connect_request()).await
     }
 
     pub async fn keepalive(conn: &Arc<Mutex<Connection>>) -> crate::Result<()> {
@@ -342,19 +477,8 @@ impl SubClient {
         // keepalive::check_response(frame.body)
     }
 
-    pub async fn subscribe(self, subscribe_req: SubscribeRequest) -> crate::Result<Subscriber> {
-        let frame = subscribe_req.into_frame();
-        let mut connection = self.connection.lock().await;
-        connection.write_frame(&frame).await?;
-        let frame: Frame<crate::Qot_Sub::Response> = match connection.read_frame().await? {
-            Some(frame) => frame,
-            None => {
-                let err = Error::new(ErrorKind::ConnectionReset, "connection reset by server");
-                return Err(err.into());
-            }
-        };
-        subscribe::check_response(frame.body)?;
-        drop(connection);
+    pub async fn subscribe(mut self, subscribe_req: SubscribeRequest) -> crate::Result<Subscriber> {
+        self.update_subscription(subscribe_req).await?;
         Ok(Subscriber { client: self })
     }
 
@@ -371,17 +495,144 @@ impl SubClient {
             None,
         );
 
-        let frame = subscribe_req.into_frame();
-        let mut connection = self.connection.lock().await;
-        connection.write_frame(&frame).await?;
-        let frame: Frame<crate::Qot_Sub::Response> = match connection.read_frame().await? {
-            Some(frame) => frame,
-            None => {
-                let err = Error::new(ErrorKind::ConnectionReset, "connection reset by server");
-                return Err(err.into());
+        self.update_subscription(subscribe_req).await
+    }
+
+    /// Sends a subscribe or unsubscribe request without giving up the client.
+    pub async fn update_subscription(
+        &mut self,
+        subscribe_req: SubscribeRequest,
+    ) -> crate::Result<()> {
+        let mut changes = Vec::new();
+        for security in subscribe_req.security_list() {
+            for sub_type in subscribe_req.sub_type_list() {
+                changes.push((security.clone(), *sub_type));
             }
-        };
-        subscribe::check_response(frame.body)
+        }
+        let is_sub = subscribe_req.is_sub_or_un_sub();
+        let is_unsub_all = subscribe_req.is_unsub_all();
+
+        if is_sub {
+            // the check is only a shortcut, OpenD still refuses what the rights don't allow
+            if self.user_info.is_none() {
+                if let Err(e) = self.get_user_info(GetUserInfoRequest::new(vec![])).await {
+                    println!("get user info error, quote rights unchecked: {:?}", e);
+                }
+            }
+            if let Some(user_info) = &self.user_info {
+                for security in subscribe_req.security_list() {
+                    user_info.check_subscribable(security, plain_security_type(security))?;
+                }
+            }
+        }
+
+        self.request(subscribe_req).await?;
+
+        if is_unsub_all {
+            self.subscriptions.clear();
+        } else if is_sub {
+            self.subscriptions.extend(changes);
+        } else {
+            for change in changes {
+                self.subscriptions.remove(&change);
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn is_subscribed(&self, security: &Security, sub_type: SubType) -> bool {
+        self.subscriptions.contains(&(security.clone(), sub_type))
+    }
+
+    fn check_subscribed(&self, security: &Security, sub_type: SubType) -> crate::Result<()> {
+        if self.is_subscribed(security, sub_type) {
+            return Ok(());
+        }
+
+        Err(format!("{} is not subscribed for {:?}", security, sub_type).into())
+    }
+
+    /// Reads until the response of `proto_id` to the request written with `serial_no` arrives,
+    /// keeping pushes for `Subscriber::next_data`.
+    async fn read_response<T: MessageFull>(
+        &mut self,
+        proto_id: u32,
+        serial_no: u32,
+    ) -> crate::Result<Frame<T>> {
+        let pending = &mut self.pending;
+        let frame_raw = self
+            .connection
+            .lock()
+            .await
+            .read_response_raw(proto_id, serial_no, |frame_raw| {
+                pending.push_back(frame_raw)
+            })
+            .await?;
+        Ok(Frame::from_raw(frame_raw)?)
+    }
+
+    /// Fetches the user info, caching it for the quote right checks done when subscribing.
+    pub async fn get_user_info(
+        &mut self,
+        get_user_info_req: GetUserInfoRequest,
+    ) -> crate::Result<GetUserInfoResponse> {
+        let get_user_info_resp = self.request(get_user_info_req).await?;
+        self.user_info = Some(get_user_info_resp.clone());
+
+        Ok(get_user_info_resp)
+    }
+
+    /// Registers or unregisters push delivery on this connection, keeping the subscription as is.
+    ///
+    /// `is_first_push` defaults to true on OpenD's side when left out.
+    pub async fn reg_qot_push(
+        &mut self,
+        security_list: Vec<Security>,
+        sub_type_list: Vec<SubType>,
+        rehab_type_list: Vec<RehabType>,
+        is_reg: bool,
+        is_first_push: Option<bool>,
+    ) -> crate::Result<()> {
+        let reg_qot_push_req = RegQotPushRequest::new(
+            security_list,
+            sub_type_list,
+            rehab_type_list,
+            is_reg,
+            is_first_push,
+        );
+        self.request(reg_qot_push_req).await?;
+        Ok(())
+    }
+
+    /// Subscriptions and quota of this connection, or of every connection with `is_req_all_conn`.
+    pub async fn get_sub_info(
+        &mut self,
+        get_sub_info_req: GetSubInfoRequest,
+    ) -> crate::Result<GetSubInfoResponse> {
+        self.request(get_sub_info_req).await
+    }
+
+    /// Reads the last `num` klines from OpenD's cache, `security` must be subscribed for `kl_type`.
+    pub async fn get_kl(
+        &mut self,
+        security: Security,
+        kl_type: KLType,
+        rehab_type: RehabType,
+        num: i32,
+    ) -> crate::Result<GetKLResponse> {
+        let get_kl_req = GetKLRequest::new(security, kl_type, rehab_type, num);
+        self.check_subscribed(get_kl_req.security(), get_kl_req.sub_type())?;
+
+        self.request(get_kl_req).await
+    }
+
+    /// Reads today's time-share from OpenD's cache, `security` must be subscribed for `SubType_RT`.
+    pub async fn get_rt(&mut self, security: Security) -> crate::Result<GetRTResponse> {
+        let get_rt_req = GetRTRequest::new(security);
+        self.check_subscribed(get_rt_req.security(), SubType::SubType_RT)?;
+
+        self.request(get_rt_req).await
     }
 }
 
@@ -394,201 +645,290 @@ impl Drop for SubClient {
 }
 
 impl QotClient {
-    async fn init_connect(&mut self) -> crate::Result<InitConnectResponse> {
-        let frame = InitConnectRequest::default().into_frame();
-        self.connection.write_frame(&frame).await?;
-        let frame: Frame<crate::InitConnect::Response> = match self.connection.read_frame().await? {
-            Some(frame) => frame,
-            None => {
-                let err = Error::new(ErrorKind::ConnectionReset, "connection reset by server");
-                return Err(err.into());
-            }
-        };
-        init_connect::check_response(frame.body)
+    /// Sends any request and reads its response, see `FutuRequest`.
+    pub async fn request<R: FutuRequest>(&mut self, req: R) -> crate::Result<R::Output> {
+        self.connection.request(req).await
+    }
+
+    /// Hands out the frames read while waiting for responses that answer no request, e.g.
+    /// Notify pushes when `ConnectOptions::recv_notify` is set, see `Connection::raw_pushes`.
+    pub fn raw_pushes(&mut self, capacity: usize) -> Receiver<FrameRaw> {
+        self.connection.raw_pushes(capacity)
+    }
+
+    /// Frames dropped because the `raw_pushes` stream was full.
+    pub fn dropped_raw_pushes(&self) -> u64 {
+        self.connection.dropped_raw_pushes()
+    }
+
+    /// Sends a generated request message of a proto not wrapped yet, e.g.
+    /// `send_raw::<Qot_GetWarrant::Request, Qot_GetWarrant::Response>(3210, req)`.
+    pub async fn send_raw<Req: MessageFull, Resp: MessageFull>(
+        &mut self,
+        proto_id: u32,
+        req: Req,
+    ) -> crate::Result<Resp> {
+        self.connection.send_raw(proto_id, req).await
+    }
+
+    async fn init_connect(
+        &mut self,
+        options: &ConnectOptions,
+    ) -> crate::Result<InitConnectResponse> {
+        self.request(options.init_connect_request()).await
     }
 
     pub async fn get_ipo_list(
         &mut self,
         get_ipo_list_req: GetIpoListRequest,
     ) -> crate::Result<GetIpoListResponse> {
-        let frame = get_ipo_list_req.into_frame();
-        self.connection.write_frame(&frame).await?;
-        let frame: Frame<crate::Qot_GetIpoList::Response> =
-            match self.connection.read_frame().await? {
-                Some(frame) => frame,
-                None => {
-                    let err = Error::new(ErrorKind::ConnectionReset, "connection reset by server");
-                    return Err(err.into());
-                }
-            };
-        ipo::check_response(frame.body)
+        self.request(get_ipo_list_req).await
     }
 
     pub async fn get_security_snapshot(
         &mut self,
         get_security_snapshot_req: GetSecuritySnapshotRequest,
     ) -> crate::Result<GetSecuritySnapshotResponse> {
-        let frame = get_security_snapshot_req.into_frame();
-        self.connection.write_frame(&frame).await?;
-        let frame: Frame<crate::Qot_GetSecuritySnapshot::Response> =
-            match self.connection.read_frame().await? {
-                Some(frame) => frame,
-                None => {
-                    let err = Error::new(ErrorKind::ConnectionReset, "connection reset by server");
-                    return Err(err.into());
-                }
-            };
-        security_snapshot::check_response(frame.body)
+        self.request(get_security_snapshot_req).await
     }
 
     pub async fn get_user_security_group(
         &mut self,
         get_user_security_group_req: GetUserSecurityGroupRequest,
     ) -> crate::Result<GetUserSecurityGroupResponse> {
-        let frame = get_user_security_group_req.into_frame();
-        self.connection.write_frame(&frame).await?;
-        let frame: Frame<crate::Qot_GetUserSecurityGroup::Response> =
-            match self.connection.read_frame().await? {
-                Some(frame) => frame,
-                None => {
-                    let err = Error::new(ErrorKind::ConnectionReset, "connection reset by server");
-                    return Err(err.into());
-                }
-            };
-        user_security_group::get::check_response(frame.body)
+        self.request(get_user_security_group_req).await
     }
 
     pub async fn get_user_security(
         &mut self,
         get_user_security_req: GetUserSecurityRequest,
     ) -> crate::Result<GetUserSecurityResponse> {
-        let frame = get_user_security_req.into_frame();
-        self.connection.write_frame(&frame).await?;
-        let frame: Frame<crate::Qot_GetUserSecurity::Response> =
-            match self.connection.read_frame().await? {
-                Some(frame) => frame,
-                None => {
-                    let err = Error::new(ErrorKind::ConnectionReset, "connection reset by server");
-                    return Err(err.into());
-                }
-            };
-        user_security::get::check_response(frame.body)
+        self.request(get_user_security_req).await
     }
 
     pub async fn modify_user_security(
         &mut self,
         modify_user_security_req: ModifyUserSecurityRequest,
     ) -> crate::Result<()> {
-        let frame = modify_user_security_req.into_frame();
-        self.connection.write_frame(&frame).await?;
-        let frame: Frame<crate::Qot_ModifyUserSecurity::Response> =
-            match self.connection.read_frame().await? {
-                Some(frame) => frame,
-                None => {
-                    let err = Error::new(ErrorKind::ConnectionReset, "connection reset by server");
-                    return Err(err.into());
-                }
-            };
-        user_security::modify::check_response(frame.body)
+        self.request(modify_user_security_req).await?;
+        Ok(())
     }
 
     pub async fn get_plate_security(
         &mut self,
         get_plate_security_req: GetPlateSecurityRequest,
     ) -> crate::Result<GetPlateSecurityResponse> {
-        let frame = get_plate_security_req.into_frame();
-        self.connection.write_frame(&frame).await?;
-        let frame: Frame<crate::Qot_GetPlateSecurity::Response> =
-            match self.connection.read_frame().await? {
-                Some(frame) => frame,
-                None => {
-                    let err = Error::new(ErrorKind::ConnectionReset, "connection reset by server");
-                    return Err(err.into());
-                }
-            };
-        plate_security::check_response(frame.body)
+        self.request(get_plate_security_req).await
     }
 
     pub async fn get_global_state(&mut self) -> crate::Result<GetGlobalStateResponse> {
-        let frame = GetGlobalStateRequest.into_frame();
-        self.connection.write_frame(&frame).await?;
-        let frame: Frame<crate::GetGlobalState::Response> =
-            match self.connection.read_frame().await? {
-                Some(frame) => frame,
-                None => {
-                    let err = Error::new(ErrorKind::ConnectionReset, "connection reset by server");
-                    return Err(err.into());
-                }
-            };
-        global_state::check_response(frame.body)
+        self.request(GetGlobalStateRequest).await
     }
 
     pub async fn get_stock_filter(
         &mut self,
         get_stock_filter_req: GetStockFilterRequest,
     ) -> crate::Result<GetStockFilterResponse> {
-        let frame = get_stock_filter_req.into_frame();
-        self.connection.write_frame(&frame).await?;
-        let frame: Frame<crate::Qot_StockFilter::Response> =
-            match self.connection.read_frame().await? {
-                Some(frame) => frame,
-                None => {
-                    let err = Error::new(ErrorKind::ConnectionReset, "connection reset by server");
-                    return Err(err.into());
-                }
-            };
-        stock_filter::check_response(frame.body)
+        self.request(get_stock_filter_req).await
     }
 
     pub async fn get_basic_qot(
         &mut self,
         get_basic_qot_req: GetBasicQotRequest,
     ) -> crate::Result<GetBasicQotResponse> {
-        let frame = get_basic_qot_req.into_frame();
-        self.connection.write_frame(&frame).await?;
-        let frame: Frame<crate::Qot_GetBasicQot::Response> =
-            match self.connection.read_frame().await? {
-                Some(frame) => frame,
-                None => {
-                    let err = Error::new(ErrorKind::ConnectionReset, "connection reset by server");
-                    return Err(err.into());
-                }
-            };
-        basic_qot::get::check_response(frame.body)
+        self.request(get_basic_qot_req).await
     }
 
     pub async fn set_price_reminder(
         &mut self,
         set_price_reminder_req: SetPriceReminderRequest,
     ) -> crate::Result<SetPriceReminderResponse> {
-        let frame = set_price_reminder_req.into_frame();
-        self.connection.write_frame(&frame).await?;
-        let frame: Frame<crate::Qot_SetPriceReminder::Response> =
-            match self.connection.read_frame().await? {
-                Some(frame) => frame,
-                None => {
-                    let err = Error::new(ErrorKind::ConnectionReset, "connection reset by server");
-                    return Err(err.into());
-                }
-            };
-        price_reminder::set::check_response(frame.body)
+        self.request(set_price_reminder_req).await
     }
 
     pub async fn get_price_reminder(
         &mut self,
         get_price_reminder_req: GetPriceReminderRequest,
     ) -> crate::Result<GetPriceReminderResponse> {
-        let frame = get_price_reminder_req.into_frame();
-        self.connection.write_frame(&frame).await?;
-        let frame: Frame<crate::Qot_GetPriceReminder::Response> =
-            match self.connection.read_frame().await? {
-                Some(frame) => frame,
-                None => {
-                    let err = Error::new(ErrorKind::ConnectionReset, "connection reset by server");
-                    return Err(err.into());
+        self.request(get_price_reminder_req).await
+    }
+
+    pub async fn get_capital_flow(
+        &mut self,
+        get_capital_flow_req: GetCapitalFlowRequest,
+    ) -> crate::Result<GetCapitalFlowResponse> {
+        self.request(get_capital_flow_req).await
+    }
+
+    pub async fn get_capital_distribution(
+        &mut self,
+        get_capital_distribution_req: GetCapitalDistributionRequest,
+    ) -> crate::Result<GetCapitalDistributionResponse> {
+        self.request(get_capital_distribution_req).await
+    }
+
+    pub async fn get_static_info(
+        &mut self,
+        get_static_info_req: GetStaticInfoRequest,
+    ) -> crate::Result<GetStaticInfoResponse> {
+        self.request(get_static_info_req).await
+    }
+
+    pub async fn get_code_change(
+        &mut self,
+        get_code_change_req: GetCodeChangeRequest,
+    ) -> crate::Result<GetCodeChangeResponse> {
+        self.request(get_code_change_req).await
+    }
+
+    pub async fn get_plate_set(
+        &mut self,
+        get_plate_set_req: GetPlateSetRequest,
+    ) -> crate::Result<GetPlateSetResponse> {
+        self.request(get_plate_set_req).await
+    }
+
+    pub async fn get_owner_plate(
+        &mut self,
+        get_owner_plate_req: GetOwnerPlateRequest,
+    ) -> crate::Result<GetOwnerPlateResponse> {
+        self.request(get_owner_plate_req).await
+    }
+
+    pub async fn get_reference(
+        &mut self,
+        get_reference_req: GetReferenceRequest,
+    ) -> crate::Result<GetReferenceResponse> {
+        self.request(get_reference_req).await
+    }
+
+    pub async fn request_rehab(
+        &mut self,
+        request_rehab_req: RequestRehabRequest,
+    ) -> crate::Result<RequestRehabResponse> {
+        self.request(request_rehab_req).await
+    }
+
+    pub async fn request_trade_date(
+        &mut self,
+        request_trade_date_req: RequestTradeDateRequest,
+    ) -> crate::Result<RequestTradeDateResponse> {
+        self.request(request_trade_date_req).await
+    }
+
+    pub async fn get_market_state(
+        &mut self,
+        get_market_state_req: GetMarketStateRequest,
+    ) -> crate::Result<GetMarketStateResponse> {
+        self.request(get_market_state_req).await
+    }
+
+    pub async fn get_suspend(
+        &mut self,
+        get_suspend_req: GetSuspendRequest,
+    ) -> crate::Result<GetSuspendResponse> {
+        self.request(get_suspend_req).await
+    }
+
+    pub async fn get_future_info(
+        &mut self,
+        get_future_info_req: GetFutureInfoRequest,
+    ) -> crate::Result<GetFutureInfoResponse> {
+        self.request(get_future_info_req).await
+    }
+
+    /// Resolves the contract currently behind a main future such as `HK.HSImain`.
+    ///
+    /// `today` is the date at the exchange, days until the last trade are counted from it.
+    pub async fn get_main_contract(
+        &mut self,
+        main: Security,
+        today: NaiveDate,
+    ) -> crate::Result<MainContract> {
+        let main_info = self
+            .get_future_info(GetFutureInfoRequest::new(vec![main.clone()]))
+            .await?
+            .into_inner()
+            .into_iter()
+            .next()
+            .ok_or_else(|| format!("no future info for {}", main))?;
+        let origin = main_info
+            .origin
+            .ok_or_else(|| format!("{} is not a main contract", main))?;
+
+        let contract = self
+            .get_future_info(GetFutureInfoRequest::new(vec![origin.clone()]))
+            .await?
+            .into_inner()
+            .into_iter()
+            .next()
+            .ok_or_else(|| format!("no future info for {}", origin))?;
+        let days_until_last_trade = contract.days_until_last_trade(today);
+
+        Ok(MainContract {
+            main,
+            contract,
+            days_until_last_trade,
+        })
+    }
+
+    pub async fn get_holding_change_list(
+        &mut self,
+        get_holding_change_list_req: GetHoldingChangeListRequest,
+    ) -> crate::Result<GetHoldingChangeListResponse> {
+        self.request(get_holding_change_list_req).await
+    }
+
+    /// Requests the chunks one after another when `max_req_security_num` is set, and merges them.
+    pub async fn get_history_kl_points(
+        &mut self,
+        get_history_kl_points_req: GetHistoryKLPointsRequest,
+    ) -> crate::Result<GetHistoryKLPointsResponse> {
+        let mut resp = GetHistoryKLPointsResponse::default();
+        let mut chunks = get_history_kl_points_req.into_chunks();
+        while let Some(chunk) = chunks.pop() {
+            let mut chunk_resp = self.request_history_kl_points(chunk.clone()).await?;
+            if chunk_resp.has_next {
+                // the response to the remaining securities tells whether more is left,
+                // a chunk that got no further keeps has_next set
+                if let Some(remaining) = chunk.remaining(&chunk_resp) {
+                    chunk_resp.has_next = false;
+                    chunks.push(remaining);
                 }
-            };
-        price_reminder::get::check_response(frame.body)
+            }
+            resp.merge(chunk_resp);
+        }
+
+        Ok(resp)
+    }
+
+    async fn request_history_kl_points(
+        &mut self,
+        get_history_kl_points_req: GetHistoryKLPointsRequest,
+    ) -> crate::Result<GetHistoryKLPointsResponse> {
+        self.request(get_history_kl_points_req).await
+    }
+
+    pub async fn get_delay_statistics(
+        &mut self,
+        get_delay_statistics_req: GetDelayStatisticsRequest,
+    ) -> crate::Result<GetDelayStatisticsResponse> {
+        self.request(get_delay_statistics_req).await
+    }
+
+    pub async fn get_user_info(
+        &mut self,
+        get_user_info_req: GetUserInfoRequest,
+    ) -> crate::Result<GetUserInfoResponse> {
+        self.request(get_user_info_req).await
+    }
+
+    pub async fn verification(
+        &mut self,
+        verification_req: VerificationRequest,
+    ) -> crate::Result<()> {
+        self.request(verification_req).await?;
+        Ok(())
     }
 }
 
@@ -604,32 +944,220 @@ pub enum UpdateResponse {
 }
 
 impl Subscriber {
+    /// Hands out the frames `next_data` doesn't recognise instead of dropping them, see
+    /// `Connection::raw_pushes`.
+    ///
+    /// They only arrive while `next_data` is being called, a full stream never holds it up.
+    pub async fn raw_pushes(&mut self, capacity: usize) -> Receiver<FrameRaw> {
+        self.client.connection.lock().await.raw_pushes(capacity)
+    }
+
+    /// Frames dropped because the `raw_pushes` stream was full.
+    pub async fn dropped_raw_pushes(&self) -> u64 {
+        self.client.connection.lock().await.dropped_raw_pushes()
+    }
+
+    pub fn client_mut(&mut self) -> &mut SubClient {
+        &mut self.client
+    }
+
+    pub async fn get_kl(
+        &mut self,
+        security: Security,
+        kl_type: KLType,
+        rehab_type: RehabType,
+        num: i32,
+    ) -> crate::Result<GetKLResponse> {
+        self.client.get_kl(security, kl_type, rehab_type, num).await
+    }
+
+    pub async fn get_rt(&mut self, security: Security) -> crate::Result<GetRTResponse> {
+        self.client.get_rt(security).await
+    }
+
+    pub async fn get_sub_info(
+        &mut self,
+        get_sub_info_req: GetSubInfoRequest,
+    ) -> crate::Result<GetSubInfoResponse> {
+        self.client.get_sub_info(get_sub_info_req).await
+    }
+
+    pub async fn get_user_info(
+        &mut self,
+        get_user_info_req: GetUserInfoRequest,
+    ) -> crate::Result<GetUserInfoResponse> {
+        self.client.get_user_info(get_user_info_req).await
+    }
+
+    pub async fn reg_qot_push(
+        &mut self,
+        security_list: Vec<Security>,
+        sub_type_list: Vec<SubType>,
+        rehab_type_list: Vec<RehabType>,
+        is_reg: bool,
+        is_first_push: Option<bool>,
+    ) -> crate::Result<()> {
+        self.client
+            .reg_qot_push(
+                security_list,
+                sub_type_list,
+                rehab_type_list,
+                is_reg,
+                is_first_push,
+            )
+            .await
+    }
+
+    /// Reads the next typed push, the frames `next_data` doesn't recognise are handed to
+    /// `raw_pushes` on the way. None means a keepalive or the connection closed.
     pub async fn next_data(&mut self) -> crate::Result<Option<UpdateResponse>> {
-        let mut connection = self.client.connection.lock().await;
-        match connection.read_frame_raw().await? {
-            Some(frame_raw) => match frame_raw.header.proto_id {
+        loop {
+            let frame_raw = match self.client.pending.pop_front() {
+                Some(frame_raw) => frame_raw,
+                None => match self.client.connection.lock().await.read_frame_raw().await? {
+                    Some(frame_raw) => frame_raw,
+                    None => return Ok(None),
+                },
+            };
+
+            match frame_raw.header.proto_id {
                 basic_qot::update::PROTO_ID => {
                     let frame: Frame<crate::Qot_UpdateBasicQot::Response> =
                         Frame::from_raw(frame_raw)?;
-                    let resp = basic_qot::update::check_response(frame.body)?;
-                    Ok(Some(UpdateResponse::BasicQot(resp)))
+                    let resp = check_ret(basic_qot::update::PROTO_ID, frame.body)?.into();
+                    return Ok(Some(UpdateResponse::BasicQot(resp)));
                 }
                 rt::update::PROTO_ID => {
                     let frame: Frame<crate::Qot_UpdateRT::Response> = Frame::from_raw(frame_raw)?;
-                    let resp = rt::update::check_response(frame.body)?;
-                    Ok(Some(UpdateResponse::RT(resp)))
+                    let resp = check_ret(rt::update::PROTO_ID, frame.body)?.into();
+                    return Ok(Some(UpdateResponse::RT(resp)));
                 }
                 kl::update::PROTO_ID => {
                     let frame: Frame<crate::Qot_UpdateKL::Response> = Frame::from_raw(frame_raw)?;
-                    let resp = kl::update::check_response(frame.body)?;
-                    Ok(Some(UpdateResponse::KL(resp)))
-                }
-                _ => {
-                    // ignore other response
-                    return Ok(None);
+                    let resp = check_ret(kl::update::PROTO_ID, frame.body)?.into();
+                    return Ok(Some(UpdateResponse::KL(resp)));
                 }
-            },
-            None => Ok(None),
+                keepalive::PROTO_ID => return Ok(None),
+                _ => self
+                    .client
+                    .connection
+                    .lock()
+                    .await
+                    .forward_raw_push(frame_raw),
+            }
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        Common::RetType,
+        Trd_Common::TrdHeader as PbTrdHeader,
+        Trd_PlaceOrder::{Response as PlaceOrderPbResponse, S2C as PlaceOrderS2C},
+        Trd_UnlockTrade::{Response as UnlockPbResponse, S2C as UnlockS2C},
+    };
+    use protobuf::MessageField;
+    use tokio::net::TcpListener;
+
+    async fn respond<M: MessageFull>(connection: &mut Connection, req: &FrameRaw, resp: M) {
+        let mut frame = Frame::new(resp, req.header.proto_id);
+        frame.header.serial_no = req.header.serial_no;
+        connection.write_frame(&frame).await.unwrap();
+    }
+
+    fn unlock_resp() -> UnlockPbResponse {
+        let mut resp = UnlockPbResponse::new();
+        resp.set_retType(RetType::RetType_Succeed as i32);
+        resp.s2c = MessageField::some(UnlockS2C::new());
+        resp
+    }
+
+    fn place_order_resp(ret_type: RetType, ret_msg: &str) -> PlaceOrderPbResponse {
+        let mut header = PbTrdHeader::new();
+        header.set_trdEnv(TrdEnv::TrdEnv_Simulate as i32);
+        header.set_accID(1);
+        header.set_trdMarket(TrdMarket::TrdMarket_HK as i32);
+        let mut s2c = PlaceOrderS2C::new();
+        s2c.header = MessageField::some(header);
+        s2c.set_orderID(42);
+
+        let mut resp = PlaceOrderPbResponse::new();
+        resp.set_retType(ret_type as i32);
+        resp.set_retMsg(ret_msg.into());
+        resp.s2c = MessageField::some(s2c);
+        resp
+    }
+
+    async fn place_order(trd_client: &mut TrdClient) -> crate::Result<PlaceOrderResponse> {
+        trd_client
+            .place_order(
+                1,
+                TrdEnv::TrdEnv_Simulate,
+                TrdMarket::TrdMarket_HK,
+                TrdSide::TrdSide_Buy,
+                OrderType::OrderType_Normal,
+                "00700".into(),
+                100.0,
+                Some(300.0),
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+            )
+            .await
+    }
+
+    #[tokio::test]
+    async fn place_order_unlocks_again_once_when_locked() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+
+        // answers the first order as locked, then accepts the unlock and the retried order
+        let opend = tokio::spawn(async move {
+            let (socket, _) = listener.accept().await.unwrap();
+            let mut connection = Connection::new(socket);
+            let mut proto_ids = Vec::new();
+            for place_order_resp in [
+                place_order_resp(RetType::RetType_Failed, "Please unlock trade first"),
+                place_order_resp(RetType::RetType_Succeed, ""),
+            ] {
+                let req = connection.read_frame_raw().await.unwrap().unwrap();
+                proto_ids.push(req.header.proto_id);
+                respond(&mut connection, &req, place_order_resp).await;
+
+                if proto_ids.len() == 1 {
+                    let req = connection.read_frame_raw().await.unwrap().unwrap();
+                    proto_ids.push(req.header.proto_id);
+                    respond(&mut connection, &req, unlock_resp()).await;
+                }
+            }
+            proto_ids
+        });
+
+        let mut trd_client = TrdClient {
+            conn_id: 1,
+            connection: Connection::new(TcpStream::connect(addr).await.unwrap()),
+            unlock_state: Some((SecurityFirm::SecurityFirm_FutuSecurities, "md5".into())),
+            need_unlock_err_codes: Vec::new(),
+        };
+
+        let place_order_resp = place_order(&mut trd_client).await.unwrap();
+        assert_eq!(place_order_resp.into_inner(), Some(42));
+        assert_eq!(
+            opend.await.unwrap(),
+            vec![
+                PlaceOrderRequest::PROTO_ID,
+                UnlockRequest::PROTO_ID,
+                PlaceOrderRequest::PROTO_ID
+            ]
+        );
+    }
+}
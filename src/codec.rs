@@ -0,0 +1,101 @@
+use crate::{
+    frame::{Error, Frame, FrameRaw, SerialNo, DEFAULT_MAX_BODY_LEN},
+    Common::ProtoFmt,
+};
+use bytes::{Buf, BytesMut};
+use protobuf::MessageFull;
+use std::{io::Cursor, sync::Arc};
+use tokio_util::codec::{Decoder, Encoder};
+
+/// Splits a byte stream into Futu frames, usable with `Framed` over any `AsyncRead + AsyncWrite`.
+///
+/// Decoded frames keep their body undecoded, `Frame::from_raw` turns them into messages.
+#[derive(Debug, Clone)]
+pub struct FutuCodec {
+    // body format of the frames encoded, decoded frames carry their own
+    proto_fmt: ProtoFmt,
+    max_body_len: u32,
+    serial_no: Arc<SerialNo>,
+    last_serial_no: u32,
+}
+
+impl Default for FutuCodec {
+    fn default() -> Self {
+        FutuCodec {
+            proto_fmt: ProtoFmt::ProtoFmt_Protobuf,
+            max_body_len: DEFAULT_MAX_BODY_LEN,
+            serial_no: Arc::new(SerialNo::new()),
+            last_serial_no: 0,
+        }
+    }
+}
+
+impl FutuCodec {
+    pub fn new(proto_fmt: ProtoFmt, max_body_len: u32) -> Self {
+        FutuCodec {
+            proto_fmt,
+            max_body_len,
+            ..Default::default()
+        }
+    }
+
+    pub fn set_proto_fmt(&mut self, proto_fmt: ProtoFmt) {
+        self.proto_fmt = proto_fmt;
+    }
+
+    pub fn set_max_body_len(&mut self, max_body_len: u32) {
+        self.max_body_len = max_body_len;
+    }
+
+    /// Serial numbers handed to frames encoded without one.
+    pub fn serial_no(&self) -> &Arc<SerialNo> {
+        &self.serial_no
+    }
+
+    /// Serial number of the frame encoded last.
+    pub fn last_serial_no(&self) -> u32 {
+        self.last_serial_no
+    }
+}
+
+impl Decoder for FutuCodec {
+    type Item = FrameRaw;
+    type Error = Error;
+
+    /// A frame failing its SHA1 check is consumed before the error is returned, so decoding
+    /// resumes at the next frame; other protocol errors leave no frame boundary to resume at.
+    fn decode(&mut self, src: &mut BytesMut) -> Result<Option<FrameRaw>, Error> {
+        let mut buf = Cursor::new(&src[..]);
+        match FrameRaw::parse(&mut buf, self.max_body_len) {
+            Ok(frame) => {
+                let len = buf.position() as usize;
+                src.advance(len);
+                Ok(Some(frame))
+            }
+            Err(Error::Incomplete) => Ok(None),
+            Err(Error::Protocol(e)) if e.is_fatal() => {
+                src.clear();
+                Err(Error::Protocol(e))
+            }
+            Err(e) => {
+                let len = buf.position() as usize;
+                src.advance(len);
+                Err(e)
+            }
+        }
+    }
+}
+
+impl<T: MessageFull> Encoder<&Frame<T>> for FutuCodec {
+    type Error = Error;
+
+    fn encode(&mut self, frame: &Frame<T>, dst: &mut BytesMut) -> Result<(), Error> {
+        let serial_no = match frame.header.serial_no {
+            0 => self.serial_no.next(),
+            serial_no => serial_no,
+        };
+        dst.extend_from_slice(&frame.to_vec_with_serial_no(self.proto_fmt, serial_no)?);
+        self.last_serial_no = serial_no;
+        Ok(())
+    }
+}
@@ -1,103 +1,392 @@
-use crate::frame::{Error, Frame, FrameRaw};
-use bytes::{Buf, BytesMut};
+use crate::{
+    codec::FutuCodec,
+    frame::{Error, Frame, FrameRaw},
+    Common::ProtoFmt,
+    FutuRequest,
+};
+use futures::{SinkExt, StreamExt};
 use protobuf::MessageFull;
-use std::io::{self, Cursor};
 use tokio::{
-    io::{AsyncReadExt, AsyncWriteExt, BufWriter},
+    io::{self, AsyncRead, AsyncWrite, AsyncWriteExt},
     net::TcpStream,
+    sync::mpsc::{self, error::TrySendError, Receiver, Sender},
     time::{timeout, Duration},
 };
+use tokio_util::codec::{Decoder, Framed};
+
+pub struct Connection<S = TcpStream> {
+    framed: Framed<S, FutuCodec>,
+
+    read_timeout: Option<Duration>,
+
+    push_timeout: Option<Duration>,
+
+    // `Framed` yields None once after a decode error
+    errored: bool,
+
+    // frames after a dropped one may already be buffered, `Framed` only decodes them on new bytes
+    resync: bool,
 
-pub struct Connection {
-    stream: BufWriter<TcpStream>,
+    // set once a frame broke the protocol, nothing is read from the stream afterwards
+    closed: bool,
 
-    buffer: BytesMut,
+    // receives the frames answering no request, see `raw_pushes`
+    raw_push_tx: Option<Sender<FrameRaw>>,
+
+    dropped_raw_pushes: u64,
 }
 
-impl Connection {
-    pub fn new(socket: TcpStream) -> Self {
+impl<S: AsyncRead + AsyncWrite + Unpin> Connection<S> {
+    pub fn new(stream: S) -> Self {
         Connection {
-            stream: BufWriter::new(socket),
-            buffer: BytesMut::with_capacity(4 * 1024),
+            framed: Framed::new(stream, FutuCodec::default()),
+            read_timeout: None,
+            push_timeout: Some(Duration::from_secs(5)),
+            errored: false,
+            resync: false,
+            closed: false,
+            raw_push_tx: None,
+            dropped_raw_pushes: 0,
         }
     }
 
+    /// Hands out the frames read while waiting for a response that answer no request, e.g.
+    /// Notify pushes, instead of dropping them, up to `capacity` of them at a time.
+    ///
+    /// Frames arriving while the stream is full are dropped and counted, see
+    /// `dropped_raw_pushes`. A later call replaces the stream.
+    pub fn raw_pushes(&mut self, capacity: usize) -> Receiver<FrameRaw> {
+        let (raw_push_tx, raw_push_rx) = mpsc::channel(capacity);
+        self.raw_push_tx = Some(raw_push_tx);
+        raw_push_rx
+    }
+
+    /// Frames dropped because the `raw_pushes` stream was full.
+    pub fn dropped_raw_pushes(&self) -> u64 {
+        self.dropped_raw_pushes
+    }
+
+    pub(crate) fn forward_raw_push(&mut self, frame_raw: FrameRaw) {
+        if let Some(raw_push_tx) = &self.raw_push_tx {
+            match raw_push_tx.try_send(frame_raw) {
+                Ok(()) => {}
+                Err(TrySendError::Full(_)) => self.dropped_raw_pushes += 1,
+                Err(TrySendError::Closed(_)) => self.raw_push_tx = None,
+            }
+        }
+    }
+
+    pub fn set_proto_fmt(&mut self, proto_fmt: ProtoFmt) {
+        self.framed.codec_mut().set_proto_fmt(proto_fmt);
+    }
+
+    /// Bounds the wait for a response, None waits forever.
+    pub fn set_read_timeout(&mut self, read_timeout: Option<Duration>) {
+        self.read_timeout = read_timeout;
+    }
+
+    /// Bounds the wait of `read_frame_raw` for a push, None waits forever.
+    pub fn set_push_timeout(&mut self, push_timeout: Option<Duration>) {
+        self.push_timeout = push_timeout;
+    }
+
+    /// Frames announcing a larger body are rejected and close the connection.
+    pub fn set_max_body_len(&mut self, max_body_len: u32) {
+        self.framed.codec_mut().set_max_body_len(max_body_len);
+    }
+
+    async fn next(
+        &mut self,
+        read_timeout: Option<Duration>,
+    ) -> Result<Option<Result<FrameRaw, Error>>, Error> {
+        match read_timeout {
+            Some(read_timeout) => timeout(read_timeout, self.framed.next())
+                .await
+                .map_err(|_| {
+                    Error::Timeout(format!("read timeout {}s", read_timeout.as_secs_f64()))
+                }),
+            None => Ok(self.framed.next().await),
+        }
+    }
+
+    async fn on_error(&mut self, e: Error) -> Error {
+        match &e {
+            Error::Protocol(protocol_error) if protocol_error.is_fatal() => {
+                self.closed = true;
+                let _ = self.framed.get_mut().shutdown().await;
+            }
+            _ => self.resync = true,
+        }
+
+        e
+    }
+
     pub async fn read_frame_raw(&mut self) -> Result<Option<FrameRaw>, Error> {
-        loop {
-            let mut buf = Cursor::new(&self.buffer[..]);
+        self.read_frame_raw_within(self.push_timeout).await
+    }
 
-            match FrameRaw::parse(&mut buf) {
-                Ok(frame) => {
-                    let len = buf.position() as usize;
-                    self.buffer.advance(len);
-                    return Ok(Some(frame));
-                }
-                Err(Error::Incomplete) => {
-                    let n = match timeout(
-                        Duration::from_secs(5),
-                        self.stream.read_buf(&mut self.buffer),
-                    )
-                    .await
-                    {
-                        Ok(Ok(n)) => n,
-                        Ok(Err(e)) => return Err(Error::ConnectionError(e.to_string())),
-                        Err(_) => {
-                            return Err(Error::Timeout("read timeout 5s".into()));
-                        }
-                    };
-
-                    if 0 == n {
-                        if self.buffer.is_empty() {
-                            // maybe server gracefully close the connection
-                            return Ok(None);
-                        } else {
-                            return Err(Error::ConnectionError("connection reset by peer".into()));
-                        }
-                    }
-                }
-                Err(e) => {
-                    return Err(e);
-                }
+    async fn read_frame_raw_within(
+        &mut self,
+        read_timeout: Option<Duration>,
+    ) -> Result<Option<FrameRaw>, Error> {
+        if self.closed {
+            return Err(Error::ConnectionError(
+                "connection closed after a protocol error".into(),
+            ));
+        }
+
+        if self.resync {
+            if self.errored {
+                self.errored = false;
+                let _ = self.framed.next().await;
+            }
+
+            let mut codec = self.framed.codec().clone();
+            match codec.decode(self.framed.read_buffer_mut()) {
+                Ok(Some(frame)) => return Ok(Some(frame)),
+                Ok(None) => self.resync = false,
+                Err(e) => return Err(self.on_error(e).await),
             }
         }
+
+        match self.next(read_timeout).await? {
+            Some(Ok(frame)) => Ok(Some(frame)),
+            Some(Err(e)) => {
+                self.errored = true;
+                Err(self.on_error(e).await)
+            }
+            // maybe server gracefully close the connection
+            None => Ok(None),
+        }
     }
 
     pub async fn read_frame<T: MessageFull>(&mut self) -> Result<Option<Frame<T>>, Error> {
-        loop {
-            let mut buf = Cursor::new(&self.buffer[..]);
+        match self.read_frame_raw_within(self.read_timeout).await? {
+            Some(frame_raw) => Ok(Some(Frame::from_raw(frame_raw)?)),
+            None => Ok(None),
+        }
+    }
 
-            match Frame::parse(&mut buf) {
-                Ok(frame) => {
-                    let len = buf.position() as usize;
-                    self.buffer.advance(len);
-                    return Ok(Some(frame));
-                }
-                Err(Error::Incomplete) => {
-                    if 0 == self
-                        .stream
-                        .read_buf(&mut self.buffer)
-                        .await
-                        .map_err(|e| Error::ConnectionError(e.to_string()))?
-                    {
-                        if self.buffer.is_empty() {
-                            return Ok(None);
-                        } else {
-                            return Err(Error::ConnectionError("connection reset by peer".into()));
-                        }
-                    }
+    /// Allocates a serial number from this connection, e.g. for a trade `PacketID`.
+    pub fn next_serial_no(&self) -> u32 {
+        self.framed.codec().serial_no().next()
+    }
+
+    /// Answers the serial number written, the response to the frame carries the same one.
+    pub async fn write_frame<T: MessageFull>(&mut self, frame: &Frame<T>) -> Result<u32, Error> {
+        self.framed.send(frame).await?;
+        Ok(self.framed.codec().last_serial_no())
+    }
+
+    /// Reads until the response of `proto_id` to the request written with `serial_no` arrives,
+    /// handing every other frame, e.g. pushes or replies given up on, to `skipped`.
+    pub async fn read_response_raw<F: FnMut(FrameRaw)>(
+        &mut self,
+        proto_id: u32,
+        serial_no: u32,
+        mut skipped: F,
+    ) -> crate::Result<FrameRaw> {
+        loop {
+            match self.read_frame_raw_within(self.read_timeout).await? {
+                Some(frame_raw)
+                    if frame_raw.header.proto_id == proto_id
+                        && frame_raw.header.serial_no == serial_no =>
+                {
+                    return Ok(frame_raw);
                 }
-                Err(e) => {
-                    return Err(e);
+                Some(frame_raw) => skipped(frame_raw),
+                None => {
+                    let err = io::Error::new(
+                        io::ErrorKind::ConnectionReset,
+                        "connection reset by server",
+                    );
+                    return Err(err.into());
                 }
             }
         }
     }
 
-    pub async fn write_frame<T: MessageFull>(&mut self, frame: &Frame<T>) -> io::Result<()> {
-        self.stream.write_all(&frame.header.to_vec()).await?;
-        self.stream
-            .write_all(frame.body.write_to_bytes()?.as_ref())
-            .await?;
-        self.stream.flush().await
+    /// Writes `req` and reads the response to it, frames answering nothing else go to
+    /// `raw_pushes`.
+    pub async fn request<R: FutuRequest>(&mut self, req: R) -> crate::Result<R::Output> {
+        self.request_with_serial_no(req, 0).await
+    }
+
+    /// Same as `request` with the frame header carrying `serial_no`, e.g. the one of a trade
+    /// `PacketID`, 0 leaves it to the connection.
+    pub async fn request_with_serial_no<R: FutuRequest>(
+        &mut self,
+        req: R,
+        serial_no: u32,
+    ) -> crate::Result<R::Output> {
+        let mut frame = req.into_frame();
+        frame.header.serial_no = serial_no;
+        let serial_no = self.write_frame(&frame).await?;
+        let frame_raw = self.read_response(R::PROTO_ID, serial_no).await?;
+        let frame: Frame<R::Response> = Frame::from_raw(frame_raw)?;
+        R::check_response(frame.body)
+    }
+
+    /// Writes a generated request message under `proto_id` and reads the response message as is,
+    /// checking `retType` is left to the caller.
+    pub async fn send_raw<Req: MessageFull, Resp: MessageFull>(
+        &mut self,
+        proto_id: u32,
+        req: Req,
+    ) -> crate::Result<Resp> {
+        let serial_no = self.write_frame(&Frame::new(req, proto_id)).await?;
+        let frame_raw = self.read_response(proto_id, serial_no).await?;
+        let frame: Frame<Resp> = Frame::from_raw(frame_raw)?;
+        Ok(frame.body)
+    }
+
+    async fn read_response(&mut self, proto_id: u32, serial_no: u32) -> crate::Result<FrameRaw> {
+        let mut skipped = Vec::new();
+        let frame_raw = self
+            .read_response_raw(proto_id, serial_no, |frame_raw| skipped.push(frame_raw))
+            .await;
+        for frame_raw in skipped {
+            self.forward_raw_push(frame_raw);
+        }
+
+        frame_raw
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        action::keepalive::KeepAliveRequest,
+        frame::{ProtocolError, DEFAULT_MAX_BODY_LEN},
+        KeepAlive::{Request, Response, S2C},
+    };
+    use protobuf::MessageField;
+    use tokio::io::{duplex, AsyncWriteExt, DuplexStream};
+
+    fn keepalive_frame(time: i64) -> Vec<u8> {
+        let mut resp = Response::new();
+        resp.set_retType(0);
+        let mut s2c = S2C::new();
+        s2c.set_time(time);
+        resp.s2c = MessageField::some(s2c);
+        let mut frame = Frame::new(resp, 1004);
+        frame.header.serial_no = 1;
+        frame.to_vec(ProtoFmt::ProtoFmt_Protobuf).unwrap()
+    }
+
+    async fn connection_with(bytes: Vec<u8>) -> (Connection<DuplexStream>, DuplexStream) {
+        let (client, mut server) = duplex(64 * 1024);
+        server.write_all(&bytes).await.unwrap();
+        (Connection::new(client), server)
+    }
+
+    async fn assert_closed_after(bytes: Vec<u8>, max_body_len: u32) -> ProtocolError {
+        let (mut connection, mut server) = connection_with(bytes).await;
+        connection.set_max_body_len(max_body_len);
+        let err = match connection.read_frame_raw().await {
+            Err(Error::Protocol(e)) => e,
+            other => panic!("expected a protocol error, got {:?}", other),
+        };
+
+        // bytes arriving after the error are never read
+        server.write_all(&keepalive_frame(2)).await.unwrap();
+        assert!(matches!(
+            connection.read_frame_raw().await,
+            Err(Error::ConnectionError(_))
+        ));
+
+        err
+    }
+
+    #[tokio::test]
+    async fn bad_header_flag_closes_the_connection() {
+        let mut bytes = keepalive_frame(1);
+        bytes[..2].copy_from_slice(b"XX");
+        let err = assert_closed_after(bytes, DEFAULT_MAX_BODY_LEN).await;
+        assert!(matches!(err, ProtocolError::HeaderFlag(flag) if &flag == b"XX"));
+    }
+
+    #[tokio::test]
+    async fn bad_proto_ver_closes_the_connection() {
+        let mut bytes = keepalive_frame(1);
+        // flag 2 bytes, proto_id 4, proto_fmt_type 1, then proto_ver
+        bytes[7] = 1;
+        let err = assert_closed_after(bytes, DEFAULT_MAX_BODY_LEN).await;
+        assert!(matches!(err, ProtocolError::ProtoVer(1)));
+    }
+
+    #[tokio::test]
+    async fn oversized_body_closes_the_connection() {
+        let bytes = keepalive_frame(1);
+        let err = assert_closed_after(bytes, 1).await;
+        assert!(matches!(
+            err,
+            ProtocolError::BodyTooLarge {
+                max_body_len: 1,
+                ..
+            }
+        ));
+    }
+
+    #[tokio::test]
+    async fn sha1_mismatch_drops_only_that_frame() {
+        let mut bytes = keepalive_frame(1);
+        // the SHA1 starts after flag, proto_id, formats, serial_no and body_len
+        bytes[16] ^= 0xff;
+        bytes.extend(keepalive_frame(2));
+        let (mut connection, _server) = connection_with(bytes).await;
+
+        assert!(matches!(
+            connection.read_frame_raw().await,
+            Err(Error::Protocol(ProtocolError::BodySha1 {
+                proto_id: 1004,
+                serial_no: 1
+            }))
+        ));
+
+        // the next frame is already buffered, no more bytes arrive
+        let frame: Frame<Response> = connection.read_frame().await.unwrap().unwrap();
+        assert_eq!(frame.body.s2c.time(), 2);
+    }
+
+    #[tokio::test]
+    async fn full_raw_pushes_drop_and_count() {
+        let (client, _server) = duplex(64);
+        let mut connection = Connection::new(client);
+        let mut raw_pushes = connection.raw_pushes(1);
+        for time in 1..=2 {
+            let bytes = keepalive_frame(time);
+            let frame_raw =
+                FrameRaw::parse(&mut std::io::Cursor::new(&bytes[..]), DEFAULT_MAX_BODY_LEN)
+                    .unwrap();
+            connection.forward_raw_push(frame_raw);
+        }
+
+        assert_eq!(connection.dropped_raw_pushes(), 1);
+        let frame: Frame<Response> = Frame::from_raw(raw_pushes.recv().await.unwrap()).unwrap();
+        assert_eq!(frame.body.s2c.time(), 1);
+    }
+
+    #[tokio::test]
+    async fn json_body_round_trips() {
+        let (client, server) = duplex(64 * 1024);
+        let mut writer = Connection::new(client);
+        writer.set_proto_fmt(ProtoFmt::ProtoFmt_Json);
+        let mut reader = Connection::new(server);
+
+        let req: Request = KeepAliveRequest::new(42).into();
+        let serial_no = writer.write_frame(&Frame::new(req, 1004)).await.unwrap();
+
+        let frame_raw = reader.read_frame_raw().await.unwrap().unwrap();
+        assert_eq!(
+            frame_raw.header.proto_fmt_type,
+            ProtoFmt::ProtoFmt_Json as u8
+        );
+        assert_eq!(frame_raw.header.serial_no, serial_no);
+        assert!(std::str::from_utf8(&frame_raw.body).is_ok());
+
+        let frame: Frame<Request> = Frame::from_raw(frame_raw).unwrap();
+        assert_eq!(frame.body.c2s.time(), 42);
     }
 }
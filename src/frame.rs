@@ -1,18 +1,38 @@
+use crate::Common::ProtoFmt;
 use bytes::Buf;
 use crypto::{digest::Digest, sha1::Sha1};
-use protobuf::MessageFull;
+use protobuf::{Enum, MessageFull};
+use protobuf_json_mapping::{ParseOptions, PrintOptions};
 use serde::{Deserialize, Serialize};
 use std::{
     fmt,
-    io::Cursor,
-    sync::{Arc, Mutex},
+    io::{self, Cursor},
+    sync::atomic::{AtomicU32, Ordering},
 };
 
 const PROTO_HEADER_LEN: usize = 44;
 const HEADER_FLAG: [u8; 2] = [70, 84]; // "FT"
+const PROTO_VER: u8 = 0;
+pub const DEFAULT_MAX_BODY_LEN: u32 = 32 * 1024 * 1024;
 
-lazy_static! {
-    static ref SERIAL_NO: Arc<Mutex<u32>> = Arc::new(Mutex::new(0));
+/// Serial numbers of one connection.
+#[derive(Debug, Default)]
+pub struct SerialNo(AtomicU32);
+
+impl SerialNo {
+    pub fn new() -> Self {
+        SerialNo::default()
+    }
+
+    /// Wraps from u32::MAX back to 1, 0 marks frames whose serial number is left to the connection.
+    pub fn next(&self) -> u32 {
+        loop {
+            let serial_no = self.0.fetch_add(1, Ordering::Relaxed).wrapping_add(1);
+            if serial_no != 0 {
+                return serial_no;
+            }
+        }
+    }
 }
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -36,12 +56,58 @@ impl APIProtoHeader {
 #[derive(Debug)]
 pub enum Error {
     Incomplete,
+    Protocol(ProtocolError),
     ProtoError(String),
     ConnectionError(String),
     Timeout(String),
     Other(String),
 }
 
+/// A frame breaking the wire protocol.
+#[derive(Debug)]
+pub enum ProtocolError {
+    HeaderFlag([u8; 2]),
+    ProtoVer(u8),
+    BodyTooLarge { body_len: u32, max_body_len: u32 },
+    BodySha1 { proto_id: u32, serial_no: u32 },
+}
+
+impl ProtocolError {
+    /// Whether frame boundaries can no longer be trusted, the connection has to be closed then.
+    ///
+    /// A SHA1 mismatch leaves the frame length intact, so only that frame is dropped.
+    pub fn is_fatal(&self) -> bool {
+        !matches!(self, ProtocolError::BodySha1 { .. })
+    }
+}
+
+impl fmt::Display for ProtocolError {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ProtocolError::HeaderFlag(flag) => write!(fmt, "invalid header flag: {:?}", flag),
+            ProtocolError::ProtoVer(proto_ver) => {
+                write!(fmt, "unsupported proto version: {}", proto_ver)
+            }
+            ProtocolError::BodyTooLarge {
+                body_len,
+                max_body_len,
+            } => write!(
+                fmt,
+                "body length {} exceeds the max {}",
+                body_len, max_body_len
+            ),
+            ProtocolError::BodySha1 {
+                proto_id,
+                serial_no,
+            } => write!(
+                fmt,
+                "body SHA1 mismatch, proto_id: {}, serial_no: {}",
+                proto_id, serial_no
+            ),
+        }
+    }
+}
+
 #[derive(Debug)]
 pub struct Frame<T: MessageFull> {
     pub header: APIProtoHeader,
@@ -56,6 +122,12 @@ pub struct FrameRaw {
 
 impl std::error::Error for Error {}
 
+impl From<io::Error> for Error {
+    fn from(e: io::Error) -> Self {
+        Error::ConnectionError(e.to_string())
+    }
+}
+
 impl fmt::Display for Error {
     fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
         write!(fmt, "{:?}", self)
@@ -70,51 +142,105 @@ fn sha1(msg: &[u8]) -> [u8; 20] {
     buf
 }
 
-fn get_header(src: &mut Cursor<&[u8]>) -> Result<APIProtoHeader, Error> {
+fn get_header(src: &mut Cursor<&[u8]>, max_body_len: u32) -> Result<APIProtoHeader, Error> {
     let remaining = src.remaining();
     if remaining < PROTO_HEADER_LEN {
         return Err(Error::Incomplete);
     }
 
-    let decoded = match bincode::deserialize(src.get_ref().get(0..PROTO_HEADER_LEN).unwrap()) {
-        Ok(d) => d,
-        Err(e) => return Err(Error::ProtoError(format!("failed to decode header: {}", e))),
-    };
+    let decoded: APIProtoHeader =
+        match bincode::deserialize(src.get_ref().get(0..PROTO_HEADER_LEN).unwrap()) {
+            Ok(d) => d,
+            Err(e) => return Err(Error::ProtoError(format!("failed to decode header: {}", e))),
+        };
+
+    if decoded.header_flag != HEADER_FLAG {
+        return Err(Error::Protocol(ProtocolError::HeaderFlag(
+            decoded.header_flag,
+        )));
+    }
+
+    if decoded.proto_ver != PROTO_VER {
+        return Err(Error::Protocol(ProtocolError::ProtoVer(decoded.proto_ver)));
+    }
+
+    if decoded.body_len > max_body_len {
+        return Err(Error::Protocol(ProtocolError::BodyTooLarge {
+            body_len: decoded.body_len,
+            max_body_len,
+        }));
+    }
 
     src.advance(PROTO_HEADER_LEN);
 
     Ok(decoded)
 }
 
-fn get_body<T: MessageFull>(src: &mut Cursor<&[u8]>, len: u32) -> Result<T, Error> {
-    let remaining = src.remaining() as u32;
-    if remaining < len {
-        return Err(Error::Incomplete);
+fn get_body<T: MessageFull>(src: &mut Cursor<&[u8]>, header: &APIProtoHeader) -> Result<T, Error> {
+    let body = get_body_raw(src, header)?;
+    decode_body(&body, header.proto_fmt_type)
+}
+
+fn decode_body<T: MessageFull>(body: &[u8], proto_fmt_type: u8) -> Result<T, Error> {
+    match ProtoFmt::from_i32(proto_fmt_type as i32) {
+        Some(ProtoFmt::ProtoFmt_Protobuf) => T::parse_from_bytes(body)
+            .map_err(|e| Error::ProtoError(format!("failed to decode body: {}", e))),
+        Some(ProtoFmt::ProtoFmt_Json) => {
+            let json = std::str::from_utf8(body)
+                .map_err(|e| Error::ProtoError(format!("failed to decode json body: {}", e)))?;
+            let parse_options = ParseOptions {
+                ignore_unknown_fields: true,
+                ..Default::default()
+            };
+            protobuf_json_mapping::parse_from_str_with_options(json, &parse_options)
+                .map_err(|e| Error::ProtoError(format!("failed to decode json body: {}", e)))
+        }
+        None => Err(Error::ProtoError(format!(
+            "Unsupported protocol format type: {}",
+            proto_fmt_type
+        ))),
     }
+}
 
-    T::parse_from_bytes(src.copy_to_bytes(len as usize).as_ref())
-        .map_err(|e| Error::ProtoError(format!("failed to decode body: {}", e)))
+fn encode_body<T: MessageFull>(body: &T, proto_fmt: ProtoFmt) -> Result<Vec<u8>, Error> {
+    match proto_fmt {
+        ProtoFmt::ProtoFmt_Protobuf => body
+            .write_to_bytes()
+            .map_err(|e| Error::ProtoError(format!("failed to encode body: {}", e))),
+        ProtoFmt::ProtoFmt_Json => {
+            let print_options = PrintOptions {
+                enum_values_int: true,
+                proto_field_name: true,
+                ..Default::default()
+            };
+            protobuf_json_mapping::print_to_string_with_options(body, &print_options)
+                .map(String::into_bytes)
+                .map_err(|e| Error::ProtoError(format!("failed to encode json body: {}", e)))
+        }
+    }
 }
 
-fn get_body_raw(src: &mut Cursor<&[u8]>, len: u32) -> Result<Vec<u8>, Error> {
+fn get_body_raw(src: &mut Cursor<&[u8]>, header: &APIProtoHeader) -> Result<Vec<u8>, Error> {
     let remaining = src.remaining() as u32;
-    if remaining < len {
+    if remaining < header.body_len {
         return Err(Error::Incomplete);
     }
 
-    Ok(src.copy_to_bytes(len as usize).to_vec())
-}
+    let body = src.copy_to_bytes(header.body_len as usize).to_vec();
+    if sha1(&body) != header.body_sha1 {
+        return Err(Error::Protocol(ProtocolError::BodySha1 {
+            proto_id: header.proto_id,
+            serial_no: header.serial_no,
+        }));
+    }
 
-pub fn serial_no() -> u32 {
-    let mut n = SERIAL_NO.lock().unwrap();
-    *n += 1;
-    *n
+    Ok(body)
 }
 
 impl FrameRaw {
-    pub fn parse(src: &mut Cursor<&[u8]>) -> Result<FrameRaw, Error> {
-        let header = get_header(src)?;
-        let body = get_body_raw(src, header.body_len)?;
+    pub fn parse(src: &mut Cursor<&[u8]>, max_body_len: u32) -> Result<FrameRaw, Error> {
+        let header = get_header(src, max_body_len)?;
+        let body = get_body_raw(src, &header)?;
 
         Ok(FrameRaw { header, body })
     }
@@ -122,43 +248,60 @@ impl FrameRaw {
 
 impl<T: MessageFull> Frame<T> {
     pub fn from_raw(frame_raw: FrameRaw) -> Result<Frame<T>, Error> {
+        let body = decode_body(&frame_raw.body, frame_raw.header.proto_fmt_type)?;
         Ok(Frame {
             header: frame_raw.header,
-            body: T::parse_from_bytes(&frame_raw.body)
-                .map_err(|e| Error::ProtoError(format!("failed to decode body: {}", e)))?,
+            body,
         })
     }
 
+    /// The header's format, length and SHA1 are placeholders until the frame is encoded.
     pub fn new(body: T, proto_id: u32) -> Frame<T> {
-        let b = body.write_to_bytes().unwrap();
-
         Frame {
             header: APIProtoHeader {
                 header_flag: HEADER_FLAG,
                 proto_id,
                 proto_fmt_type: 0, // 0: protobuf 1: json
-                proto_ver: 0,
-                serial_no: serial_no(),
-                body_len: b.len() as u32,
-                body_sha1: sha1(&b),
+                proto_ver: PROTO_VER,
+                serial_no: 0, // assigned by the connection when written
+                body_len: 0,
+                body_sha1: Default::default(),
                 reserved: Default::default(),
             },
             body,
         }
     }
 
-    pub fn parse(src: &mut Cursor<&[u8]>) -> Result<Frame<T>, Error> {
-        let header = get_header(src)?;
-        if header.proto_fmt_type != 0 {
-            return Err(Error::ProtoError(
-                "Unsupported protocol format type: json".into(),
-            ));
-        }
+    /// Encodes header and body in `proto_fmt`, the header's length and SHA1 follow the encoded body.
+    pub fn to_vec(&self, proto_fmt: ProtoFmt) -> Result<Vec<u8>, Error> {
+        self.to_vec_with_serial_no(proto_fmt, self.header.serial_no)
+    }
 
-        let body_len = header.body_len;
-        Ok(Frame {
-            header,
-            body: get_body(src, body_len)?,
-        })
+    pub(crate) fn to_vec_with_serial_no(
+        &self,
+        proto_fmt: ProtoFmt,
+        serial_no: u32,
+    ) -> Result<Vec<u8>, Error> {
+        let body = encode_body(&self.body, proto_fmt)?;
+        let header = APIProtoHeader {
+            header_flag: self.header.header_flag,
+            proto_id: self.header.proto_id,
+            proto_fmt_type: proto_fmt as u8,
+            proto_ver: self.header.proto_ver,
+            serial_no,
+            body_len: body.len() as u32,
+            body_sha1: sha1(&body),
+            reserved: self.header.reserved,
+        };
+
+        let mut buf = header.to_vec();
+        buf.extend(body);
+        Ok(buf)
+    }
+
+    pub fn parse(src: &mut Cursor<&[u8]>, max_body_len: u32) -> Result<Frame<T>, Error> {
+        let header = get_header(src, max_body_len)?;
+        let body = get_body(src, &header)?;
+        Ok(Frame { header, body })
     }
 }
@@ -2,19 +2,27 @@
 #![allow(clippy::from_over_into)]
 #![allow(clippy::too_many_arguments)]
 
-#[macro_use]
-extern crate lazy_static;
-
 include!(concat!(env!("OUT_DIR"), "/rust_protobuf_protos/mod.rs"));
 
 pub mod action;
+pub mod calendar;
+pub use calendar::TradingCalendar;
 pub mod client;
+pub mod codec;
 pub use client::UpdateResponse;
+pub use codec::FutuCodec;
 pub mod connection;
 pub use connection::Connection;
 pub mod frame;
-pub use frame::serial_no;
 pub use frame::Frame;
+pub mod options;
+pub use options::ConnectOptions;
+pub mod request;
+pub use request::{Ack, FutuRequest, ResponseError};
+pub mod sub_manager;
+pub use sub_manager::SubscriptionManager;
+#[cfg(test)]
+mod test_support;
 
 pub type Error = Box<dyn std::error::Error + Send + Sync>;
 pub type Result<T> = std::result::Result<T, Error>;
@@ -0,0 +1,122 @@
+use crate::{
+    action::init_connect::InitConnectRequest, frame::DEFAULT_MAX_BODY_LEN, Common::ProtoFmt,
+};
+use tokio::time::Duration;
+
+/// Options used when connecting to OpenD, shared by `qot_connect`, `sub_connect` and `trd_connect`.
+#[derive(Debug, Clone)]
+pub struct ConnectOptions {
+    client_ver: i32,
+    client_id: String,
+    recv_notify: bool,
+    push_proto_fmt: Option<ProtoFmt>,
+    proto_fmt: ProtoFmt,
+    connect_timeout: Option<Duration>,
+    read_timeout: Option<Duration>,
+    push_timeout: Option<Duration>,
+    max_body_len: u32,
+}
+
+impl Default for ConnectOptions {
+    fn default() -> Self {
+        ConnectOptions {
+            client_ver: 221,
+            client_id: "800".into(),
+            recv_notify: false,
+            push_proto_fmt: None,
+            proto_fmt: ProtoFmt::ProtoFmt_Protobuf,
+            connect_timeout: None,
+            read_timeout: None,
+            push_timeout: Some(Duration::from_secs(5)),
+            max_body_len: DEFAULT_MAX_BODY_LEN,
+        }
+    }
+}
+
+impl ConnectOptions {
+    pub fn new() -> Self {
+        ConnectOptions::default()
+    }
+
+    pub fn client_ver(mut self, client_ver: i32) -> Self {
+        self.client_ver = client_ver;
+        self
+    }
+
+    /// Tells processes sharing one OpenD apart.
+    pub fn client_id(mut self, client_id: impl Into<String>) -> Self {
+        self.client_id = client_id.into();
+        self
+    }
+
+    /// Receives OpenD's system notifications on this connection, they come out of the
+    /// client's raw pushes.
+    pub fn recv_notify(mut self, recv_notify: bool) -> Self {
+        self.recv_notify = recv_notify;
+        self
+    }
+
+    /// Format of the pushes on this connection, OpenD's `push_proto_type` config applies when unset.
+    pub fn push_proto_fmt(mut self, push_proto_fmt: ProtoFmt) -> Self {
+        self.push_proto_fmt = Some(push_proto_fmt);
+        self
+    }
+
+    /// Format of the request bodies sent on this connection, responses are decoded by their header.
+    pub fn proto_fmt(mut self, proto_fmt: ProtoFmt) -> Self {
+        self.proto_fmt = proto_fmt;
+        self
+    }
+
+    pub fn connect_timeout(mut self, connect_timeout: Duration) -> Self {
+        self.connect_timeout = Some(connect_timeout);
+        self
+    }
+
+    /// Bounds the wait for a response, None waits forever.
+    pub fn read_timeout(mut self, read_timeout: Option<Duration>) -> Self {
+        self.read_timeout = read_timeout;
+        self
+    }
+
+    /// Bounds the wait of a subscriber for the next push, None waits forever.
+    pub fn push_timeout(mut self, push_timeout: Option<Duration>) -> Self {
+        self.push_timeout = push_timeout;
+        self
+    }
+
+    /// Frames announcing a larger body close the connection with a protocol error.
+    pub fn max_body_len(mut self, max_body_len: u32) -> Self {
+        self.max_body_len = max_body_len;
+        self
+    }
+
+    pub fn get_connect_timeout(&self) -> Option<Duration> {
+        self.connect_timeout
+    }
+
+    pub fn get_read_timeout(&self) -> Option<Duration> {
+        self.read_timeout
+    }
+
+    pub fn get_push_timeout(&self) -> Option<Duration> {
+        self.push_timeout
+    }
+
+    pub fn get_max_body_len(&self) -> u32 {
+        self.max_body_len
+    }
+
+    pub fn get_proto_fmt(&self) -> ProtoFmt {
+        self.proto_fmt
+    }
+
+    pub fn init_connect_request(&self) -> InitConnectRequest {
+        InitConnectRequest::new(
+            self.client_ver,
+            self.client_id.clone(),
+            self.recv_notify,
+            self.push_proto_fmt,
+        )
+    }
+}
@@ -0,0 +1,86 @@
+use crate::{Common::RetType, Frame};
+use protobuf::{reflect::ReflectValueRef, MessageFull};
+use std::fmt;
+
+/// A request to OpenD, tying the protobuf messages of a proto to the domain type answered.
+///
+/// Implementing it for a new type is all `client.request` needs to call a proto.
+pub trait FutuRequest: Into<Self::Request> {
+    const PROTO_ID: u32;
+
+    type Request: MessageFull;
+    type Response: MessageFull;
+    type Output: From<Self::Response>;
+
+    /// Fails with a `ResponseError` unless `retType` is a success.
+    fn check_response(resp: Self::Response) -> crate::Result<Self::Output> {
+        Ok(check_ret(Self::PROTO_ID, resp)?.into())
+    }
+
+    fn into_frame(self) -> Frame<Self::Request> {
+        Frame::new(self.into(), Self::PROTO_ID)
+    }
+}
+
+/// Output of the requests answered with nothing but their `retType`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Ack;
+
+impl<M: MessageFull> From<M> for Ack {
+    fn from(_: M) -> Self {
+        Ack
+    }
+}
+
+/// A response whose `retType` is not a success, with the `retMsg` and `errCode` it carried.
+#[derive(Debug, Clone)]
+pub struct ResponseError {
+    pub proto_id: u32,
+    pub ret_type: i32,
+    pub ret_msg: String,
+    pub err_code: Option<i32>,
+}
+
+impl fmt::Display for ResponseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.ret_type, self.ret_msg)
+    }
+}
+
+impl std::error::Error for ResponseError {}
+
+/// Answers `resp` as is when its `retType` is a success, every Futu response carries the
+/// `retType`, `retMsg` and `errCode` fields.
+pub fn check_ret<M: MessageFull>(proto_id: u32, resp: M) -> Result<M, ResponseError> {
+    let descriptor = M::descriptor();
+    let field = |name: &str| {
+        descriptor
+            .field_by_name(name)
+            .and_then(|field| field.get_singular(&resp))
+    };
+
+    // retType is required with a default of RetType_Unknown
+    let ret_type = match field("retType") {
+        Some(ReflectValueRef::I32(ret_type)) => ret_type,
+        _ => RetType::RetType_Unknown as i32,
+    };
+    if ret_type == RetType::RetType_Succeed as i32 {
+        return Ok(resp);
+    }
+
+    let ret_msg = match field("retMsg") {
+        Some(ReflectValueRef::String(ret_msg)) => ret_msg.to_owned(),
+        _ => String::new(),
+    };
+    let err_code = match field("errCode") {
+        Some(ReflectValueRef::I32(err_code)) => Some(err_code),
+        _ => None,
+    };
+
+    Err(ResponseError {
+        proto_id,
+        ret_type,
+        ret_msg,
+        err_code,
+    })
+}
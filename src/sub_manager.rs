@@ -0,0 +1,315 @@
+use crate::{
+    action::{common::Security, sub_info::GetSubInfoRequest, subscribe::SubscribeRequest},
+    client::SubClient,
+    Qot_Common::SubType,
+};
+use std::{
+    collections::{BTreeMap, HashMap},
+    time::{Duration, Instant},
+};
+
+/// OpenD refuses to unsubscribe within one minute of subscribing.
+const MIN_SUBSCRIBE_DURATION: Duration = Duration::from_secs(60);
+
+type SubKey = (Security, SubType);
+
+/// Reference counted subscriptions shared by several consumers of one `SubClient`.
+///
+/// `add` subscribes only the pairs nobody holds yet, `remove` drops a reference and
+/// unsubscribes the pairs nobody holds anymore once OpenD allows it. Pairs that are still
+/// too young stay subscribed until a later `remove` or `sync`.
+#[derive(Debug, Default)]
+pub struct SubscriptionManager {
+    ref_counts: HashMap<SubKey, usize>,
+    subscribed_at: HashMap<SubKey, Instant>,
+    remain_quota: Option<i32>,
+}
+
+impl SubscriptionManager {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Fetches the remaining subscription quota from OpenD.
+    pub async fn refresh_quota(&mut self, client: &mut SubClient) -> crate::Result<i32> {
+        let remain_quota = client
+            .get_sub_info(GetSubInfoRequest::new(None))
+            .await?
+            .remain_quota;
+        self.remain_quota = Some(remain_quota);
+        Ok(remain_quota)
+    }
+
+    pub fn remain_quota(&self) -> Option<i32> {
+        self.remain_quota
+    }
+
+    pub fn ref_count(&self, security: &Security, sub_type: SubType) -> usize {
+        self.ref_counts
+            .get(&(security.clone(), sub_type))
+            .copied()
+            .unwrap_or(0)
+    }
+
+    pub fn is_subscribed(&self, security: &Security, sub_type: SubType) -> bool {
+        self.subscribed_at
+            .contains_key(&(security.clone(), sub_type))
+    }
+
+    /// Takes a reference on every (security, sub type) pair, subscribing the new ones.
+    ///
+    /// Fails without changing anything when the new pairs exceed the remaining quota. When
+    /// subscribing a later sub type fails, the sub types subscribed before it are kept
+    /// unreferenced, so `sync` unsubscribes them once OpenD allows it, and no reference is
+    /// taken.
+    pub async fn add(
+        &mut self,
+        client: &mut SubClient,
+        security_list: Vec<Security>,
+        sub_type_list: Vec<SubType>,
+    ) -> crate::Result<()> {
+        let keys = pairs(security_list, sub_type_list);
+        let new_keys = self.new_keys(&keys);
+
+        if !new_keys.is_empty() {
+            let remain_quota = match self.remain_quota {
+                Some(remain_quota) => remain_quota,
+                None => self.refresh_quota(client).await?,
+            };
+            if new_keys.len() as i32 > remain_quota {
+                return Err(format!(
+                    "subscribing {} more pairs exceeds the remaining quota {}",
+                    new_keys.len(),
+                    remain_quota
+                )
+                .into());
+            }
+
+            for (sub_type, security_list) in group_by_sub_type(&new_keys) {
+                let subscribe_req = SubscribeRequest::new(
+                    security_list,
+                    vec![sub_type],
+                    true,
+                    Some(true),
+                    Vec::new(),
+                    None,
+                    None,
+                    None,
+                    None,
+                );
+                if let Err(err) = client.update_subscription(subscribe_req).await {
+                    self.requota(client).await;
+                    return Err(err);
+                }
+
+                let subscribed: Vec<SubKey> = new_keys
+                    .iter()
+                    .filter(|(_, t)| *t == sub_type)
+                    .cloned()
+                    .collect();
+                self.remain_quota = self
+                    .remain_quota
+                    .map(|remain_quota| remain_quota - subscribed.len() as i32);
+                self.mark_subscribed(subscribed, Instant::now());
+            }
+        }
+
+        self.take(keys);
+
+        Ok(())
+    }
+
+    /// Drops a reference on every pair, then unsubscribes what is no longer referenced.
+    pub async fn remove(
+        &mut self,
+        client: &mut SubClient,
+        security_list: Vec<Security>,
+        sub_type_list: Vec<SubType>,
+    ) -> crate::Result<()> {
+        self.release(pairs(security_list, sub_type_list));
+        self.sync(client).await
+    }
+
+    /// Unsubscribes the unreferenced pairs that have been subscribed for at least one minute.
+    pub async fn sync(&mut self, client: &mut SubClient) -> crate::Result<()> {
+        let stale = self.stale(Instant::now());
+
+        for (sub_type, security_list) in group_by_sub_type(&stale) {
+            let subscribe_req = SubscribeRequest::new(
+                security_list,
+                vec![sub_type],
+                false,
+                None,
+                Vec::new(),
+                None,
+                None,
+                None,
+                None,
+            );
+            if let Err(err) = client.update_subscription(subscribe_req).await {
+                self.requota(client).await;
+                return Err(err);
+            }
+
+            let mut released = 0;
+            for key in stale.iter().filter(|(_, t)| *t == sub_type) {
+                self.subscribed_at.remove(key);
+                released += 1;
+            }
+            self.remain_quota = self
+                .remain_quota
+                .map(|remain_quota| remain_quota + released);
+        }
+
+        Ok(())
+    }
+
+    // after a failed (un)subscribe the counted quota can't be trusted, ask OpenD again and
+    // leave it unknown until the next `add` when that fails too
+    async fn requota(&mut self, client: &mut SubClient) {
+        if self.refresh_quota(client).await.is_err() {
+            self.remain_quota = None;
+        }
+    }
+
+    fn new_keys(&self, keys: &[SubKey]) -> Vec<SubKey> {
+        keys.iter()
+            .filter(|key| !self.subscribed_at.contains_key(key))
+            .cloned()
+            .collect()
+    }
+
+    fn mark_subscribed(&mut self, keys: Vec<SubKey>, now: Instant) {
+        for key in keys {
+            self.subscribed_at.insert(key, now);
+        }
+    }
+
+    fn take(&mut self, keys: Vec<SubKey>) {
+        for key in keys {
+            *self.ref_counts.entry(key).or_insert(0) += 1;
+        }
+    }
+
+    fn release(&mut self, keys: Vec<SubKey>) {
+        for key in keys {
+            if let Some(count) = self.ref_counts.get_mut(&key) {
+                *count -= 1;
+                if *count == 0 {
+                    self.ref_counts.remove(&key);
+                }
+            }
+        }
+    }
+
+    fn stale(&self, now: Instant) -> Vec<SubKey> {
+        self.subscribed_at
+            .iter()
+            .filter(|(key, subscribed_at)| {
+                !self.ref_counts.contains_key(*key)
+                    && now.duration_since(**subscribed_at) >= MIN_SUBSCRIBE_DURATION
+            })
+            .map(|(key, _)| key.clone())
+            .collect()
+    }
+
+    /// Unreferenced pairs still waiting for the one-minute minimum to pass.
+    pub fn pending_unsubscribe(&self) -> Vec<(Security, SubType)> {
+        self.subscribed_at
+            .keys()
+            .filter(|key| !self.ref_counts.contains_key(*key))
+            .cloned()
+            .collect()
+    }
+}
+
+fn pairs(security_list: Vec<Security>, sub_type_list: Vec<SubType>) -> Vec<SubKey> {
+    let mut keys = Vec::new();
+    for security in security_list {
+        for sub_type in &sub_type_list {
+            let key = (security.clone(), *sub_type);
+            if !keys.contains(&key) {
+                keys.push(key);
+            }
+        }
+    }
+
+    keys
+}
+
+fn group_by_sub_type(keys: &[SubKey]) -> Vec<(SubType, Vec<Security>)> {
+    let mut groups: BTreeMap<i32, (SubType, Vec<Security>)> = BTreeMap::new();
+    for (security, sub_type) in keys {
+        groups
+            .entry(*sub_type as i32)
+            .or_insert_with(|| (*sub_type, Vec::new()))
+            .1
+            .push(security.clone());
+    }
+
+    groups.into_values().collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{test_support::security, Qot_Common::QotMarket};
+
+    const HK: QotMarket = QotMarket::QotMarket_HK_Security;
+
+    fn keys(codes: &[&str], sub_type_list: &[SubType]) -> Vec<SubKey> {
+        pairs(
+            codes.iter().map(|code| security(HK, code)).collect(),
+            sub_type_list.to_vec(),
+        )
+    }
+
+    #[test]
+    fn counts_references_per_pair() {
+        let mut manager = SubscriptionManager::new();
+        let both = keys(&["00700", "09988"], &[SubType::SubType_Basic]);
+        manager.mark_subscribed(manager.new_keys(&both), Instant::now());
+        manager.take(both.clone());
+
+        // the second consumer only needs a reference, nothing is new
+        let tencent = keys(&["00700"], &[SubType::SubType_Basic]);
+        assert!(manager.new_keys(&tencent).is_empty());
+        manager.take(tencent.clone());
+        assert_eq!(
+            manager.ref_count(&security(HK, "00700"), SubType::SubType_Basic),
+            2
+        );
+
+        manager.release(tencent);
+        assert_eq!(
+            manager.ref_count(&security(HK, "00700"), SubType::SubType_Basic),
+            1
+        );
+        assert!(manager.pending_unsubscribe().is_empty());
+
+        manager.release(both);
+        assert_eq!(
+            manager.ref_count(&security(HK, "00700"), SubType::SubType_Basic),
+            0
+        );
+        assert_eq!(manager.pending_unsubscribe().len(), 2);
+        assert!(manager.is_subscribed(&security(HK, "00700"), SubType::SubType_Basic));
+    }
+
+    #[test]
+    fn keeps_pairs_for_the_minimum_duration() {
+        let mut manager = SubscriptionManager::new();
+        let now = Instant::now();
+        let basic = keys(&["00700"], &[SubType::SubType_Basic]);
+        manager.mark_subscribed(basic.clone(), now);
+        manager.take(basic.clone());
+        assert!(manager.stale(now + MIN_SUBSCRIBE_DURATION).is_empty());
+
+        manager.release(basic.clone());
+        assert!(manager.stale(now).is_empty());
+        assert!(manager
+            .stale(now + MIN_SUBSCRIBE_DURATION - Duration::from_secs(1))
+            .is_empty());
+        assert_eq!(manager.stale(now + MIN_SUBSCRIBE_DURATION), basic);
+    }
+}
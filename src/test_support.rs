@@ -0,0 +1,86 @@
+//! Fixtures shared by the unit tests.
+
+use crate::{
+    action::{
+        common::{KLine, Rehab, Security},
+        suspend::Suspend,
+        trade_date::TradeDate,
+    },
+    Qot_Common::{QotMarket, TradeDateType},
+};
+use chrono::NaiveDate;
+
+/// Parses "yyyy-MM-dd".
+pub fn date(date: &str) -> NaiveDate {
+    NaiveDate::parse_from_str(date, "%Y-%m-%d").unwrap()
+}
+
+/// A kline with every price at `close_price`.
+pub fn kline(time: &str, last_close_price: f64, close_price: f64) -> KLine {
+    KLine {
+        time: time.into(),
+        is_blank: false,
+        high_price: Some(close_price),
+        open_price: Some(close_price),
+        low_price: Some(close_price),
+        close_price: Some(close_price),
+        last_close_price: Some(last_close_price),
+        volume: None,
+        turnover: None,
+        turnover_rate: None,
+        pe: None,
+        change_rate: None,
+        timestamp: None,
+    }
+}
+
+/// A rehab event with only its forward and backward factors `(a, b)`.
+pub fn rehab(time: &str, fwd: (f64, f64), bwd: (f64, f64)) -> Rehab {
+    Rehab {
+        time: time.into(),
+        company_act_flag: 0,
+        fwd_factor_a: fwd.0,
+        fwd_factor_b: fwd.1,
+        bwd_factor_a: bwd.0,
+        bwd_factor_b: bwd.1,
+        split_base: None,
+        split_ert: None,
+        join_base: None,
+        join_ert: None,
+        bonus_base: None,
+        bonus_ert: None,
+        transfer_base: None,
+        transfer_ert: None,
+        allot_base: None,
+        allot_ert: None,
+        allot_price: None,
+        add_base: None,
+        add_ert: None,
+        add_price: None,
+        dividend: None,
+        sp_dividend: None,
+        timestamp: None,
+    }
+}
+
+pub fn trade_date(time: &str, trade_date_type: TradeDateType) -> TradeDate {
+    TradeDate {
+        time: time.into(),
+        timestamp: None,
+        trade_date_type,
+    }
+}
+
+pub fn suspend(time: &str) -> Suspend {
+    Suspend {
+        time: time.into(),
+        timestamp: None,
+    }
+}
+
+pub fn security(market: QotMarket, code: &str) -> Security {
+    Security {
+        market,
+        code: code.into(),
+    }
+}